@@ -0,0 +1,910 @@
+//! Conversion of the public data files to Arrow record batches and Parquet files.
+//!
+//! Rows are buffered into batches of a fixed size, so memory use does not depend on the size of
+//! the input. Timestamps are stored as UTC millisecond timestamps, and enumerations are stored as
+//! dictionary columns using the values from the public data (with empty values as nulls).
+use crate::model::{
+    NoteEntry, NoteStatusHistoryEntry, Status, enrollment::UserEnrollment, rating::RatingEntry,
+};
+use arrow::{
+    array::{
+        ArrayBuilder, ArrayRef, BooleanBuilder, StringBuilder, StringDictionaryBuilder,
+        TimestampMillisecondBuilder, UInt8Builder, UInt32Builder, UInt64Builder,
+    },
+    datatypes::{DataType, Field, Int8Type, Schema, SchemaRef, TimeUnit},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use chrono::{DateTime, Utc};
+use parquet::{
+    arrow::ArrowWriter,
+    basic::{Compression, ZstdLevel},
+    errors::ParquetError,
+    file::properties::WriterProperties,
+};
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+pub const DEFAULT_BATCH_SIZE: usize = 64 * 1024;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Arrow error")]
+    Arrow(#[from] ArrowError),
+    #[error("Parquet error")]
+    Parquet(#[from] ParquetError),
+    #[error("TSV error")]
+    Tsv(#[from] crate::tsv::Error),
+}
+
+/// Column builders for one of the public data files.
+pub trait Table: Default {
+    type Row<'a>;
+
+    fn schema() -> SchemaRef;
+    fn append(&mut self, row: &Self::Row<'_>) -> Result<(), ArrowError>;
+    /// The number of rows appended since the last batch was finished.
+    fn len(&self) -> usize;
+    fn finish(&mut self) -> Result<RecordBatch, ArrowError>;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Writes rows to a Parquet file in batches.
+pub struct Writer<W: Write + Send, T> {
+    inner: ArrowWriter<W>,
+    table: T,
+    batch_size: usize,
+    row_count: usize,
+}
+
+impl<W: Write + Send, T: Table> Writer<W, T> {
+    pub fn new(writer: W, batch_size: usize) -> Result<Self, Error> {
+        let properties = WriterProperties::builder()
+            .set_max_row_group_size(batch_size)
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .build();
+
+        Ok(Self {
+            inner: ArrowWriter::try_new(writer, T::schema(), Some(properties))?,
+            table: T::default(),
+            batch_size,
+            row_count: 0,
+        })
+    }
+
+    pub fn write(&mut self, row: &T::Row<'_>) -> Result<(), Error> {
+        self.table.append(row)?;
+        self.row_count += 1;
+
+        if self.table.len() >= self.batch_size {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        if !self.table.is_empty() {
+            self.inner.write(&self.table.finish()?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write any buffered rows and the file footer, returning the number of rows written.
+    pub fn close(mut self) -> Result<usize, Error> {
+        self.flush()?;
+        self.inner.close()?;
+
+        Ok(self.row_count)
+    }
+}
+
+/// Convert a public data file to Parquet, returning the number of rows written.
+pub fn convert<R: Read, W: Write + Send, T: Table>(
+    reader: &mut crate::tsv::Reader<R>,
+    writer: W,
+    batch_size: usize,
+) -> Result<usize, Error>
+where
+    for<'a> T::Row<'a>: serde::de::Deserialize<'a>,
+{
+    let mut writer = Writer::<W, T>::new(writer, batch_size)?;
+
+    while let Some(row) = reader.read::<T::Row<'_>>()? {
+        writer.write(&row)?;
+    }
+
+    writer.close()
+}
+
+fn timestamp_type() -> DataType {
+    DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+}
+
+fn dictionary_type() -> DataType {
+    DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8))
+}
+
+struct TimestampColumn(TimestampMillisecondBuilder);
+
+impl Default for TimestampColumn {
+    fn default() -> Self {
+        Self(TimestampMillisecondBuilder::new().with_timezone("UTC"))
+    }
+}
+
+impl TimestampColumn {
+    fn append(&mut self, value: Option<DateTime<Utc>>) {
+        self.0
+            .append_option(value.map(|value| value.timestamp_millis()));
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        Arc::new(self.0.finish())
+    }
+}
+
+/// A dictionary column for an enumeration, using its serialized names.
+struct EnumColumn<T> {
+    builder: StringDictionaryBuilder<Int8Type>,
+    /// Names are looked up once for each value.
+    names: Vec<(T, Option<String>)>,
+}
+
+impl<T> Default for EnumColumn<T> {
+    fn default() -> Self {
+        Self {
+            builder: StringDictionaryBuilder::new(),
+            names: vec![],
+        }
+    }
+}
+
+impl<T: Copy + PartialEq + serde::Serialize> EnumColumn<T> {
+    fn append(&mut self, value: Option<T>) -> Result<(), ArrowError> {
+        let name = match value {
+            Some(value) => {
+                let index = self
+                    .names
+                    .iter()
+                    .position(|(known, _)| *known == value)
+                    .unwrap_or_else(|| {
+                        let name = match serde_json::to_value(value) {
+                            Ok(serde_json::Value::String(name)) if !name.is_empty() => Some(name),
+                            _ => None,
+                        };
+
+                        self.names.push((value, name));
+                        self.names.len() - 1
+                    });
+
+                self.names[index].1.as_deref()
+            }
+            None => None,
+        };
+
+        if let Some(name) = name {
+            self.builder.append(name).map(|_| ())
+        } else {
+            self.builder.append_null();
+
+            Ok(())
+        }
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        Arc::new(self.builder.finish())
+    }
+}
+
+type NoteFlag = for<'a> fn(&NoteEntry<'a>) -> bool;
+
+const NOTE_FLAGS: [(&str, NoteFlag); 13] = [
+    ("misleading_other", |row| row.misleading_other),
+    ("misleading_factual_error", |row| {
+        row.misleading_factual_error
+    }),
+    ("misleading_manipulated_media", |row| {
+        row.misleading_manipulated_media
+    }),
+    ("misleading_outdated_information", |row| {
+        row.misleading_outdated_information
+    }),
+    ("misleading_missing_important_context", |row| {
+        row.misleading_missing_important_context
+    }),
+    ("misleading_unverified_claim_as_fact", |row| {
+        row.misleading_unverified_claim_as_fact
+    }),
+    ("misleading_satire", |row| row.misleading_satire),
+    ("not_misleading_other", |row| row.not_misleading_other),
+    ("not_misleading_factually_correct", |row| {
+        row.not_misleading_factually_correct
+    }),
+    ("not_misleading_outdated_but_not_when_written", |row| {
+        row.not_misleading_outdated_but_not_when_written
+    }),
+    ("not_misleading_clearly_satire", |row| {
+        row.not_misleading_clearly_satire
+    }),
+    ("not_misleading_personal_opinion", |row| {
+        row.not_misleading_personal_opinion
+    }),
+    ("trustworthy_sources", |row| row.trustworthy_sources),
+];
+
+#[derive(Default)]
+pub struct Notes {
+    note_id: UInt64Builder,
+    participant_id: StringBuilder,
+    created_at: TimestampColumn,
+    tweet_id: UInt64Builder,
+    classification: EnumColumn<crate::model::Classification>,
+    believable: EnumColumn<crate::model::Believable>,
+    harmful: EnumColumn<crate::model::Harmful>,
+    validation_difficulty: EnumColumn<crate::model::ValidationDifficulty>,
+    flags: [BooleanBuilder; NOTE_FLAGS.len()],
+    summary: StringBuilder,
+    is_media_note: BooleanBuilder,
+}
+
+impl Table for Notes {
+    type Row<'a> = NoteEntry<'a>;
+
+    fn schema() -> SchemaRef {
+        let mut fields = vec![
+            Field::new("note_id", DataType::UInt64, false),
+            Field::new("participant_id", DataType::Utf8, false),
+            Field::new("created_at", timestamp_type(), false),
+            Field::new("tweet_id", DataType::UInt64, true),
+            Field::new("classification", dictionary_type(), true),
+            Field::new("believable", dictionary_type(), true),
+            Field::new("harmful", dictionary_type(), true),
+            Field::new("validation_difficulty", dictionary_type(), true),
+        ];
+
+        fields.extend(
+            NOTE_FLAGS
+                .iter()
+                .map(|(name, _)| Field::new(*name, DataType::Boolean, false)),
+        );
+        fields.push(Field::new("summary", DataType::Utf8, false));
+        fields.push(Field::new("is_media_note", DataType::Boolean, false));
+
+        Arc::new(Schema::new(fields))
+    }
+
+    fn append(&mut self, row: &Self::Row<'_>) -> Result<(), ArrowError> {
+        self.note_id.append_value(row.note_id);
+        self.participant_id.append_value(&row.participant_id);
+        self.created_at.append(Some(row.created_at));
+        self.tweet_id.append_option(row.tweet_id);
+        self.classification.append(Some(row.classification))?;
+        self.believable.append(Some(row.believable))?;
+        self.harmful.append(Some(row.harmful))?;
+        self.validation_difficulty
+            .append(Some(row.validation_difficulty))?;
+
+        for (builder, (_, flag)) in self.flags.iter_mut().zip(NOTE_FLAGS) {
+            builder.append_value(flag(row));
+        }
+
+        self.summary.append_value(&row.summary);
+        self.is_media_note.append_value(row.is_media_note);
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.note_id.len()
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch, ArrowError> {
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(self.note_id.finish()),
+            Arc::new(self.participant_id.finish()),
+            self.created_at.finish(),
+            Arc::new(self.tweet_id.finish()),
+            self.classification.finish(),
+            self.believable.finish(),
+            self.harmful.finish(),
+            self.validation_difficulty.finish(),
+        ];
+
+        columns.extend(
+            self.flags
+                .iter_mut()
+                .map(|builder| Arc::new(builder.finish()) as ArrayRef),
+        );
+        columns.push(Arc::new(self.summary.finish()));
+        columns.push(Arc::new(self.is_media_note.finish()));
+
+        RecordBatch::try_new(Self::schema(), columns)
+    }
+}
+
+type RatingFlag = for<'a> fn(&RatingEntry<'a>) -> bool;
+
+const RATING_FLAGS: [(&str, RatingFlag); 26] = [
+    ("agree", |row| row.agree),
+    ("disagree", |row| row.disagree),
+    ("helpful", |row| row.helpful),
+    ("not_helpful", |row| row.not_helpful),
+    ("helpful_other", |row| row.helpful_other),
+    ("helpful_informative", |row| row.helpful_informative),
+    ("helpful_clear", |row| row.helpful_clear),
+    ("helpful_empathetic", |row| row.helpful_empathetic),
+    ("helpful_good_sources", |row| row.helpful_good_sources),
+    ("helpful_unique_context", |row| row.helpful_unique_context),
+    ("helpful_addresses_claim", |row| row.helpful_addresses_claim),
+    ("helpful_important_context", |row| {
+        row.helpful_important_context
+    }),
+    ("helpful_unbiased_language", |row| {
+        row.helpful_unbiased_language
+    }),
+    ("not_helpful_other", |row| row.not_helpful_other),
+    ("not_helpful_incorrect", |row| row.not_helpful_incorrect),
+    ("not_helpful_sources_missing_or_unreliable", |row| {
+        row.not_helpful_sources_missing_or_unreliable
+    }),
+    ("not_helpful_opinion_speculation_or_bias", |row| {
+        row.not_helpful_opinion_speculation_or_bias
+    }),
+    ("not_helpful_missing_key_points", |row| {
+        row.not_helpful_missing_key_points
+    }),
+    ("not_helpful_outdated", |row| row.not_helpful_outdated),
+    ("not_helpful_hard_to_understand", |row| {
+        row.not_helpful_hard_to_understand
+    }),
+    ("not_helpful_argumentative_or_biased", |row| {
+        row.not_helpful_argumentative_or_biased
+    }),
+    ("not_helpful_off_topic", |row| row.not_helpful_off_topic),
+    ("not_helpful_spam_harassment_or_abuse", |row| {
+        row.not_helpful_spam_harassment_or_abuse
+    }),
+    ("not_helpful_irrelevant_sources", |row| {
+        row.not_helpful_irrelevant_sources
+    }),
+    ("not_helpful_opinion_speculation", |row| {
+        row.not_helpful_opinion_speculation
+    }),
+    ("not_helpful_note_not_needed", |row| {
+        row.not_helpful_note_not_needed
+    }),
+];
+
+#[derive(Default)]
+pub struct Ratings {
+    note_id: UInt64Builder,
+    participant_id: StringBuilder,
+    created_at: TimestampColumn,
+    version: UInt8Builder,
+    helpfulness_level: EnumColumn<crate::model::rating::HelpfulnessLevel>,
+    flags: [BooleanBuilder; RATING_FLAGS.len()],
+    rated_on_tweet_id: UInt64Builder,
+}
+
+impl Table for Ratings {
+    type Row<'a> = RatingEntry<'a>;
+
+    fn schema() -> SchemaRef {
+        let mut fields = vec![
+            Field::new("note_id", DataType::UInt64, false),
+            Field::new("participant_id", DataType::Utf8, false),
+            Field::new("created_at", timestamp_type(), false),
+            Field::new("version", DataType::UInt8, false),
+            Field::new("helpfulness_level", dictionary_type(), true),
+        ];
+
+        fields.extend(
+            RATING_FLAGS
+                .iter()
+                .map(|(name, _)| Field::new(*name, DataType::Boolean, false)),
+        );
+        fields.push(Field::new("rated_on_tweet_id", DataType::UInt64, true));
+
+        Arc::new(Schema::new(fields))
+    }
+
+    fn append(&mut self, row: &Self::Row<'_>) -> Result<(), ArrowError> {
+        self.note_id.append_value(row.note_id);
+        self.participant_id.append_value(&row.participant_id);
+        self.created_at.append(Some(row.created_at));
+        self.version.append_value(row.version);
+        self.helpfulness_level.append(Some(row.helpfulness_level))?;
+
+        for (builder, (_, flag)) in self.flags.iter_mut().zip(RATING_FLAGS) {
+            builder.append_value(flag(row));
+        }
+
+        self.rated_on_tweet_id.append_option(row.rated_on_tweet_id);
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.note_id.len()
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch, ArrowError> {
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(self.note_id.finish()),
+            Arc::new(self.participant_id.finish()),
+            self.created_at.finish(),
+            Arc::new(self.version.finish()),
+            self.helpfulness_level.finish(),
+        ];
+
+        columns.extend(
+            self.flags
+                .iter_mut()
+                .map(|builder| Arc::new(builder.finish()) as ArrayRef),
+        );
+        columns.push(Arc::new(self.rated_on_tweet_id.finish()));
+
+        RecordBatch::try_new(Self::schema(), columns)
+    }
+}
+
+type StatusTimestamp = for<'a> fn(&NoteStatusHistoryEntry<'a>) -> Option<DateTime<Utc>>;
+type StatusValue = for<'a> fn(&NoteStatusHistoryEntry<'a>) -> Option<Status>;
+
+const STATUS_TIMESTAMPS: [(&str, StatusTimestamp); 9] = [
+    ("first_non_nmr_status_at", |row| row.first_non_nmr_status_at),
+    ("current_status_at", |row| row.current_status_at),
+    ("most_recent_non_nmr_status_at", |row| {
+        row.most_recent_non_nmr_status_at
+    }),
+    ("status_locked_at", |row| row.status_locked_at),
+    ("retro_locked_at", |row| row.retro_locked_at),
+    ("most_recent_status_change_at", |row| {
+        row.most_recent_status_change_at
+    }),
+    ("nmr_due_to_min_stable_crh_time_at", |row| {
+        row.nmr_due_to_min_stable_crh_time_at
+    }),
+    ("first_nmr_due_to_min_stable_crh_time_at", |row| {
+        row.first_nmr_due_to_min_stable_crh_time_at
+    }),
+    ("final_scoring_output_at", |row| row.final_scoring_output_at),
+];
+
+const STATUS_VALUES: [(&str, StatusValue); 8] = [
+    ("first_non_nmr_status", |row| row.first_non_nmr_status),
+    ("current_status", |row| Some(row.current_status)),
+    ("most_recent_non_nmr_status", |row| {
+        row.most_recent_non_nmr_status
+    }),
+    ("locked_status", |row| row.locked_status),
+    ("current_core_status", |row| row.current_core_status),
+    ("current_expansion_status", |row| {
+        row.current_expansion_status
+    }),
+    ("current_group_status", |row| row.current_group_status),
+    ("current_multi_group_status", |row| {
+        row.current_multi_group_status
+    }),
+];
+
+#[derive(Default)]
+pub struct StatusHistory {
+    note_id: UInt64Builder,
+    participant_id: StringBuilder,
+    created_at: TimestampColumn,
+    timestamps: [TimestampColumn; STATUS_TIMESTAMPS.len()],
+    statuses: [EnumColumn<Status>; STATUS_VALUES.len()],
+    first_decided_by: EnumColumn<birdsite::model::graphql::birdwatch::note::model::Model>,
+    current_decided_by: EnumColumn<birdsite::model::graphql::birdwatch::note::model::Model>,
+    current_modeling_group: UInt8Builder,
+    current_modeling_multi_group: UInt8Builder,
+}
+
+impl Table for StatusHistory {
+    type Row<'a> = NoteStatusHistoryEntry<'a>;
+
+    fn schema() -> SchemaRef {
+        let mut fields = vec![
+            Field::new("note_id", DataType::UInt64, false),
+            Field::new("participant_id", DataType::Utf8, false),
+            Field::new("created_at", timestamp_type(), false),
+        ];
+
+        fields.extend(
+            STATUS_TIMESTAMPS
+                .iter()
+                .map(|(name, _)| Field::new(*name, timestamp_type(), true)),
+        );
+        fields.extend(
+            STATUS_VALUES
+                .iter()
+                .map(|(name, _)| Field::new(*name, dictionary_type(), true)),
+        );
+        fields.push(Field::new("first_decided_by", dictionary_type(), true));
+        fields.push(Field::new("current_decided_by", dictionary_type(), true));
+        fields.push(Field::new("current_modeling_group", DataType::UInt8, true));
+        fields.push(Field::new(
+            "current_modeling_multi_group",
+            DataType::UInt8,
+            true,
+        ));
+
+        Arc::new(Schema::new(fields))
+    }
+
+    fn append(&mut self, row: &Self::Row<'_>) -> Result<(), ArrowError> {
+        self.note_id.append_value(row.note_id);
+        self.participant_id.append_value(&row.participant_id);
+        self.created_at.append(Some(row.created_at));
+
+        for (column, (_, timestamp)) in self.timestamps.iter_mut().zip(STATUS_TIMESTAMPS) {
+            column.append(timestamp(row));
+        }
+
+        for (column, (_, status)) in self.statuses.iter_mut().zip(STATUS_VALUES) {
+            column.append(status(row))?;
+        }
+
+        self.first_decided_by.append(row.first_decided_by)?;
+        self.current_decided_by.append(row.current_decided_by)?;
+        self.current_modeling_group
+            .append_option(row.current_modeling_group);
+        self.current_modeling_multi_group
+            .append_option(row.current_modeling_multi_group);
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.note_id.len()
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch, ArrowError> {
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(self.note_id.finish()),
+            Arc::new(self.participant_id.finish()),
+            self.created_at.finish(),
+        ];
+
+        columns.extend(self.timestamps.iter_mut().map(TimestampColumn::finish));
+        columns.extend(self.statuses.iter_mut().map(EnumColumn::finish));
+        columns.push(self.first_decided_by.finish());
+        columns.push(self.current_decided_by.finish());
+        columns.push(Arc::new(self.current_modeling_group.finish()));
+        columns.push(Arc::new(self.current_modeling_multi_group.finish()));
+
+        RecordBatch::try_new(Self::schema(), columns)
+    }
+}
+
+#[derive(Default)]
+pub struct Enrollments {
+    participant_id: StringBuilder,
+    enrollment_state: EnumColumn<crate::model::enrollment::EnrollmentState>,
+    successful_rating_needed_to_earn_in: UInt32Builder,
+    last_state_change_at: TimestampColumn,
+    last_earn_out_at: TimestampColumn,
+    modeling_population: EnumColumn<crate::model::enrollment::ModelingPopulation>,
+    modeling_group: UInt8Builder,
+    number_of_times_earned_out: UInt32Builder,
+}
+
+impl Table for Enrollments {
+    type Row<'a> = UserEnrollment<'a>;
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("participant_id", DataType::Utf8, false),
+            Field::new("enrollment_state", dictionary_type(), true),
+            Field::new(
+                "successful_rating_needed_to_earn_in",
+                DataType::UInt32,
+                false,
+            ),
+            Field::new("last_state_change_at", timestamp_type(), false),
+            Field::new("last_earn_out_at", timestamp_type(), true),
+            Field::new("modeling_population", dictionary_type(), true),
+            Field::new("modeling_group", DataType::UInt8, true),
+            Field::new("number_of_times_earned_out", DataType::UInt32, false),
+        ]))
+    }
+
+    fn append(&mut self, row: &Self::Row<'_>) -> Result<(), ArrowError> {
+        self.participant_id.append_value(&row.participant_id);
+        self.enrollment_state.append(Some(row.enrollment_state))?;
+        self.successful_rating_needed_to_earn_in
+            .append_value(row.successful_rating_needed_to_earn_in);
+        self.last_state_change_at
+            .append(Some(row.last_state_change_at));
+        self.last_earn_out_at.append(row.last_earn_out_at);
+        self.modeling_population
+            .append(Some(row.modeling_population))?;
+        self.modeling_group.append_option(row.modeling_group);
+        self.number_of_times_earned_out
+            .append_value(row.number_of_times_earned_out);
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.participant_id.len()
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch, ArrowError> {
+        RecordBatch::try_new(
+            Self::schema(),
+            vec![
+                Arc::new(self.participant_id.finish()),
+                self.enrollment_state.finish(),
+                Arc::new(self.successful_rating_needed_to_earn_in.finish()),
+                self.last_state_change_at.finish(),
+                self.last_earn_out_at.finish(),
+                self.modeling_population.finish(),
+                Arc::new(self.modeling_group.finish()),
+                Arc::new(self.number_of_times_earned_out.finish()),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Enrollments, Notes, Ratings, StatusHistory, Table};
+    use crate::model::fixtures::{NOTE_STATUS_HISTORY, NOTES, RATINGS, USER_ENROLLMENT};
+    use arrow::{
+        array::{Array, AsArray, RecordBatch, TimestampMillisecondArray},
+        datatypes::{Int8Type, UInt8Type, UInt64Type},
+    };
+    use parquet::{
+        arrow::arrow_reader::ParquetRecordBatchReaderBuilder,
+        basic::{Compression, ZstdLevel},
+    };
+
+    /// Convert a file and read it back (as a single batch).
+    fn round_trip<T: Table>(tsv: &str) -> RecordBatch
+    where
+        for<'a> T::Row<'a>: serde::de::Deserialize<'a>,
+    {
+        let mut file = tempfile::tempfile().unwrap();
+        let mut reader = crate::tsv::Reader::new(tsv.as_bytes()).unwrap();
+
+        let count = super::convert::<_, _, T>(
+            &mut reader,
+            file.try_clone().unwrap(),
+            super::DEFAULT_BATCH_SIZE,
+        )
+        .unwrap();
+
+        std::io::Seek::rewind(&mut file).unwrap();
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+
+        assert_eq!(
+            builder.metadata().row_group(0).column(0).compression(),
+            Compression::ZSTD(ZstdLevel::default())
+        );
+
+        let mut batches = builder
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(batches.len(), 1);
+
+        let batch = batches.pop().unwrap();
+
+        assert_eq!(batch.num_rows(), count);
+        assert_eq!(batch.schema(), T::schema());
+
+        batch
+    }
+
+    /// The name of an enumeration value (if it is not null).
+    fn enum_value(batch: &RecordBatch, name: &str, index: usize) -> Option<String> {
+        let column = batch
+            .column_by_name(name)
+            .unwrap()
+            .as_dictionary::<Int8Type>();
+
+        column.is_valid(index).then(|| {
+            column
+                .values()
+                .as_string::<i32>()
+                .value(column.keys().value(index).try_into().unwrap())
+                .to_string()
+        })
+    }
+
+    #[test]
+    fn convert_enrollment_to_parquet() {
+        let mut file = tempfile::tempfile().unwrap();
+        let mut reader = crate::tsv::Reader::new(USER_ENROLLMENT.as_bytes()).unwrap();
+
+        // A small batch size to exercise multiple batches.
+        let count =
+            super::convert::<_, _, Enrollments>(&mut reader, file.try_clone().unwrap(), 2).unwrap();
+
+        assert_eq!(count, 3);
+
+        std::io::Seek::rewind(&mut file).unwrap();
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+
+        assert_eq!(builder.metadata().num_row_groups(), 2);
+
+        let batches = builder
+            .with_batch_size(2)
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].schema(), Enrollments::schema());
+
+        let states = batches[1].column(1).as_dictionary::<Int8Type>();
+        let values = states.values().as_string::<i32>();
+
+        assert_eq!(
+            values.value(states.keys().value(0).try_into().unwrap()),
+            "newUser"
+        );
+
+        let last_earn_out_at = batches[0]
+            .column(4)
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap();
+
+        assert!(last_earn_out_at.is_null(0));
+        assert_eq!(batches[1].column(6).null_count(), 1);
+    }
+
+    #[test]
+    fn batches_match_schemas() {
+        let note = crate::model::NoteEntry {
+            classification: crate::model::Classification::NotMisleading,
+            not_misleading_factually_correct: true,
+            trustworthy_sources: true,
+            summary: "Context".into(),
+            ..crate::model::fixtures::note_entry(1, "A")
+        };
+
+        let mut notes = Notes::default();
+        notes.append(&note).unwrap();
+        let batch = notes.finish().unwrap();
+
+        assert_eq!(batch.num_rows(), 1);
+        assert_eq!(batch.column(3).null_count(), 1);
+        assert_eq!(batch.column(5).null_count(), 1);
+
+        let status = serde_json::from_str::<crate::model::NoteStatusHistoryEntry<'_>>(
+            r#"{"noteId":1,"noteAuthorParticipantId":"A","createdAtMillis":0,"currentStatus":"CURRENTLY_RATED_HELPFUL","currentDecidedBy":"CoreModel (v1.1)"}"#,
+        )
+        .unwrap();
+
+        let mut status_history = StatusHistory::default();
+        status_history.append(&status).unwrap();
+        let batch = status_history.finish().unwrap();
+
+        let decided_by = batch
+            .column_by_name("current_decided_by")
+            .unwrap()
+            .as_dictionary::<Int8Type>();
+
+        assert_eq!(
+            decided_by.values().as_string::<i32>().value(0),
+            "CoreModel (v1.1)"
+        );
+        assert_eq!(
+            batch
+                .column_by_name("first_decided_by")
+                .unwrap()
+                .null_count(),
+            1
+        );
+    }
+
+    #[test]
+    fn convert_notes_to_parquet() {
+        let batch = round_trip::<Notes>(NOTES);
+        let tweet_ids = batch
+            .column_by_name("tweet_id")
+            .unwrap()
+            .as_primitive::<UInt64Type>();
+        let summaries = batch.column_by_name("summary").unwrap().as_string::<i32>();
+
+        assert_eq!(batch.num_rows(), 2);
+        assert!(tweet_ids.is_null(0));
+        assert_eq!(tweet_ids.value(1), 1_699_999_999_999_999_999);
+        assert_eq!(
+            enum_value(&batch, "classification", 0).as_deref(),
+            Some("MISINFORMED_OR_POTENTIALLY_MISLEADING")
+        );
+        assert_eq!(enum_value(&batch, "believable", 1), None);
+        assert_eq!(
+            summaries.value(0),
+            "The claim is \"false\";\tsee https://example.com/"
+        );
+    }
+
+    #[test]
+    fn convert_ratings_to_parquet() {
+        let batch = round_trip::<Ratings>(RATINGS);
+        let versions = batch
+            .column_by_name("version")
+            .unwrap()
+            .as_primitive::<UInt8Type>();
+        let rated_on_tweet_ids = batch
+            .column_by_name("rated_on_tweet_id")
+            .unwrap()
+            .as_primitive::<UInt64Type>();
+        let helpful_informative = batch
+            .column_by_name("helpful_informative")
+            .unwrap()
+            .as_boolean();
+
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(versions.values().to_vec(), vec![1, 2]);
+        assert_eq!(enum_value(&batch, "helpfulness_level", 0), None);
+        assert_eq!(
+            enum_value(&batch, "helpfulness_level", 1).as_deref(),
+            Some("NOT_HELPFUL")
+        );
+        assert!(helpful_informative.value(0));
+        assert!(!helpful_informative.value(1));
+        assert!(rated_on_tweet_ids.is_null(0));
+        assert_eq!(rated_on_tweet_ids.value(1), 1_400_247_230_330_667_009);
+    }
+
+    #[test]
+    fn convert_status_history_to_parquet() {
+        let batch = round_trip::<StatusHistory>(NOTE_STATUS_HISTORY);
+
+        assert_eq!(batch.num_rows(), 1);
+        assert_eq!(
+            enum_value(&batch, "first_non_nmr_status", 0).as_deref(),
+            Some("CURRENTLY_RATED_HELPFUL")
+        );
+        assert_eq!(
+            enum_value(&batch, "locked_status", 0).as_deref(),
+            Some("CURRENTLY_RATED_NOT_HELPFUL")
+        );
+        assert_eq!(enum_value(&batch, "current_expansion_status", 0), None);
+        assert_eq!(
+            enum_value(&batch, "first_decided_by", 0).as_deref(),
+            Some("ExpansionModel (v1.1)")
+        );
+        assert_eq!(
+            enum_value(&batch, "current_decided_by", 0).as_deref(),
+            Some("CoreModel (v1.1)")
+        );
+        assert_eq!(
+            batch
+                .column_by_name("retro_locked_at")
+                .unwrap()
+                .null_count(),
+            1
+        );
+
+        let modeling_groups = batch
+            .column_by_name("current_modeling_group")
+            .unwrap()
+            .as_primitive::<UInt8Type>();
+
+        assert_eq!(modeling_groups.value(0), 0);
+        assert!(
+            batch
+                .column_by_name("current_modeling_multi_group")
+                .unwrap()
+                .is_null(0)
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! Per-contributor aggregates computed from the public data files.
+use crate::model::{
+    NoteEntry, NoteStatusHistoryEntry, Status,
+    enrollment::{EnrollmentState, UserEnrollment},
+    rating::{HelpfulnessLevel, RatingEntry},
+};
+use birdsite::model::graphql::birdwatch::profile::Profile;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Contributor<'a> {
+    /// Notes in the notes file.
+    pub note_count: usize,
+    /// Notes in the status history file, by current status.
+    pub helpful_note_count: usize,
+    pub not_helpful_note_count: usize,
+    pub needs_more_ratings_note_count: usize,
+    pub rating_count: usize,
+    pub helpful_rating_count: usize,
+    pub somewhat_helpful_rating_count: usize,
+    pub not_helpful_rating_count: usize,
+    pub enrollment_state: Option<EnrollmentState>,
+    /// A scraped profile (if one has been added).
+    pub profile: Option<&'a Profile<'a>>,
+}
+
+impl Contributor<'_> {
+    #[must_use]
+    pub fn alias(&self) -> Option<&str> {
+        self.profile.and_then(|profile| profile.alias.as_deref())
+    }
+
+    #[must_use]
+    pub const fn status_count(&self) -> usize {
+        self.helpful_note_count + self.not_helpful_note_count + self.needs_more_ratings_note_count
+    }
+
+    /// The proportion of notes (with a recorded status) that are currently rated helpful.
+    // Counts will never be large enough to lose precision.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn helpful_rate(&self) -> Option<f64> {
+        let count = self.status_count();
+
+        (count > 0).then(|| self.helpful_note_count as f64 / count as f64)
+    }
+}
+
+/// Contributors keyed by participant ID.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Contributors<'a> {
+    contributors: BTreeMap<String, Contributor<'a>>,
+}
+
+impl<'a> Contributors<'a> {
+    fn entry(&mut self, participant_id: &str) -> &mut Contributor<'a> {
+        self.contributors
+            .entry(participant_id.to_string())
+            .or_default()
+    }
+
+    pub fn add_note(&mut self, entry: &NoteEntry<'_>) {
+        self.entry(&entry.participant_id).note_count += 1;
+    }
+
+    pub fn add_status(&mut self, entry: &NoteStatusHistoryEntry<'_>) {
+        let contributor = self.entry(&entry.participant_id);
+
+        match entry.current_status {
+            Status::Helpful => contributor.helpful_note_count += 1,
+            Status::NotHelpful => contributor.not_helpful_note_count += 1,
+            Status::NeedsMoreRatings => contributor.needs_more_ratings_note_count += 1,
+        }
+    }
+
+    pub fn add_rating(&mut self, entry: &RatingEntry<'_>) {
+        let contributor = self.entry(&entry.participant_id);
+
+        contributor.rating_count += 1;
+
+        match entry.level() {
+            Some(HelpfulnessLevel::Helpful) => contributor.helpful_rating_count += 1,
+            Some(HelpfulnessLevel::SomewhatHelpful) => {
+                contributor.somewhat_helpful_rating_count += 1;
+            }
+            Some(HelpfulnessLevel::NotHelpful) => contributor.not_helpful_rating_count += 1,
+            Some(HelpfulnessLevel::Empty) | None => {}
+        }
+    }
+
+    pub fn add_enrollment(&mut self, entry: &UserEnrollment<'_>) {
+        self.entry(&entry.participant_id).enrollment_state = Some(entry.enrollment_state);
+    }
+
+    /// Scraped profiles do not include participant IDs, so the caller must provide the mapping.
+    ///
+    /// Profiles are only added for contributors that appear in the public data.
+    pub fn add_profile(&mut self, participant_id: &str, profile: &'a Profile<'a>) -> bool {
+        self.contributors
+            .get_mut(participant_id)
+            .map(|contributor| contributor.profile = Some(profile))
+            .is_some()
+    }
+
+    #[must_use]
+    pub fn get(&self, participant_id: &str) -> Option<&Contributor<'a>> {
+        self.contributors.get(participant_id)
+    }
+
+    /// The contributor with a profile with the given alias (if any).
+    #[must_use]
+    pub fn get_by_alias(&self, alias: &str) -> Option<(&str, &Contributor<'a>)> {
+        self.iter()
+            .find(|(_, contributor)| contributor.alias() == Some(alias))
+    }
+
+    /// All contributors, ordered by participant ID.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Contributor<'a>)> {
+        self.contributors
+            .iter()
+            .map(|(participant_id, contributor)| (participant_id.as_str(), contributor))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.contributors.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.contributors.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Contributors;
+    use crate::model::{
+        enrollment::EnrollmentState,
+        fixtures::{RATINGS, USER_ENROLLMENT, note_entry, status_history_entry},
+        rating::RatingEntry,
+    };
+    use birdsite::model::graphql::birdwatch::profile::Profile;
+
+    #[test]
+    fn aggregate_contributors() {
+        let profile = serde_json::from_str::<Profile<'_>>(
+            r#"{"alias":"brave-blue-heron","ratings_count":null,"notes_count":null,"deleted_notes_count":null,"has_notes":true,"is_top_writer":false}"#,
+        )
+        .unwrap();
+
+        let mut contributors = Contributors::default();
+
+        for entry in [
+            status_history_entry(1, "A", "CURRENTLY_RATED_HELPFUL"),
+            status_history_entry(2, "A", "NEEDS_MORE_RATINGS"),
+            status_history_entry(3, "A", "CURRENTLY_RATED_HELPFUL"),
+            status_history_entry(4, "A", "CURRENTLY_RATED_NOT_HELPFUL"),
+        ] {
+            contributors.add_status(&entry);
+        }
+
+        let mut reader = crate::tsv::Reader::new(USER_ENROLLMENT.as_bytes()).unwrap();
+
+        while let Some(entry) = reader.read().unwrap() {
+            contributors.add_enrollment(&entry);
+        }
+
+        assert!(contributors.add_profile("A", &profile));
+        assert!(!contributors.add_profile("C", &profile));
+
+        let (participant_id, a) = contributors.get_by_alias("brave-blue-heron").unwrap();
+
+        assert_eq!(participant_id, "A");
+        assert_eq!(a.status_count(), 4);
+        assert_eq!(a.helpful_rate(), Some(0.5));
+        // The latest enrollment row for a participant wins.
+        assert_eq!(a.enrollment_state, Some(EnrollmentState::AtRisk));
+
+        let b = contributors.get("B").unwrap();
+
+        assert_eq!(b.helpful_rate(), None);
+        assert_eq!(b.alias(), None);
+        assert_eq!(contributors.len(), 2);
+    }
+
+    #[test]
+    fn count_notes_and_ratings() {
+        let mut contributors = Contributors::default();
+
+        contributors.add_note(&note_entry(1, "A"));
+        contributors.add_note(&note_entry(2, "A"));
+
+        let mut reader = crate::tsv::Reader::new(RATINGS.as_bytes()).unwrap();
+
+        while let Some(entry) = reader.read::<RatingEntry<'_>>().unwrap() {
+            contributors.add_rating(&entry);
+        }
+
+        let a = contributors.get("A").unwrap();
+
+        assert_eq!(a.note_count, 2);
+        assert_eq!(a.rating_count, 0);
+
+        let first_rater = contributors
+            .get("4874E27F86D8AC0AC3AB85FF754C25BE")
+            .unwrap();
+
+        assert_eq!(first_rater.rating_count, 1);
+        assert_eq!(first_rater.helpful_rating_count, 1);
+        assert_eq!(first_rater.note_count, 0);
+
+        let second_rater = contributors
+            .get("F1B3F0D8EA3F1A8F1C3A6E7D5B4C2A10")
+            .unwrap();
+
+        assert_eq!(second_rater.rating_count, 1);
+        assert_eq!(second_rater.not_helpful_rating_count, 1);
+        assert_eq!(contributors.len(), 3);
+    }
+}
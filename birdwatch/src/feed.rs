@@ -0,0 +1,488 @@
+//! A feed of note status changes between daily bundles.
+//!
+//! Only a compact snapshot of the most recent statuses is kept between updates, so each update
+//! only needs to read the status history file of the new bundle. The changes for each day are
+//! appended to an NDJSON file.
+use crate::model::{NoteStatusHistoryEntry, Status};
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+const SNAPSHOT_FILE_NAME: &str = "snapshot.json";
+const CHANGES_FILE_NAME: &str = "changes.ndjson";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error")]
+    Json(#[from] serde_json::Error),
+    #[error("Snapshot is not older than update")]
+    OutOfOrder {
+        snapshot: NaiveDate,
+        update: NaiveDate,
+    },
+}
+
+/// The status of a single note in a snapshot.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct NoteState {
+    #[serde(rename = "s", with = "status_code")]
+    pub status: Status,
+    #[serde(
+        rename = "l",
+        with = "optional_status_code",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub locked_status: Option<Status>,
+}
+
+/// The statuses of all notes in a bundle.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Snapshot {
+    pub date: NaiveDate,
+    pub notes: BTreeMap<u64, NoteState>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChangeKind {
+    Created {
+        #[serde(with = "status_code")]
+        status: Status,
+    },
+    BecameHelpful {
+        #[serde(with = "status_code")]
+        previous: Status,
+    },
+    LostHelpful {
+        #[serde(with = "status_code")]
+        current: Status,
+    },
+    /// A change that does not involve helpful status.
+    StatusChanged {
+        #[serde(with = "status_code")]
+        previous: Status,
+        #[serde(with = "status_code")]
+        current: Status,
+    },
+    Locked {
+        #[serde(with = "status_code")]
+        status: Status,
+    },
+    /// The note is no longer in the public data (usually because it was deleted).
+    Disappeared {
+        #[serde(with = "status_code")]
+        last_status: Status,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Change {
+    #[serde(rename = "id")]
+    pub note_id: u64,
+    #[serde(flatten)]
+    pub kind: ChangeKind,
+}
+
+/// The changes between two consecutive snapshots.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DailyChanges {
+    pub date: NaiveDate,
+    /// Missing for the first snapshot (in which case every note is reported as created).
+    pub previous_date: Option<NaiveDate>,
+    pub changes: Vec<Change>,
+}
+
+impl Snapshot {
+    pub fn new<'a, I: IntoIterator<Item = &'a NoteStatusHistoryEntry<'a>>>(
+        date: NaiveDate,
+        entries: I,
+    ) -> Self {
+        let mut snapshot = Self::empty(date);
+
+        for entry in entries {
+            snapshot.insert(entry);
+        }
+
+        snapshot
+    }
+
+    #[must_use]
+    pub const fn empty(date: NaiveDate) -> Self {
+        Self {
+            date,
+            notes: BTreeMap::new(),
+        }
+    }
+
+    /// Add a single status history row (for example while streaming the status history file).
+    pub fn insert(&mut self, entry: &NoteStatusHistoryEntry<'_>) {
+        self.notes.insert(
+            entry.note_id,
+            NoteState {
+                status: entry.current_status,
+                locked_status: entry.locked_status,
+            },
+        );
+    }
+
+    /// The changes from this snapshot to a later one.
+    #[must_use]
+    pub fn diff(&self, next: &Self) -> DailyChanges {
+        let mut changes = diff_notes(&self.notes, &next.notes);
+
+        for (note_id, state) in &self.notes {
+            if !next.notes.contains_key(note_id) {
+                changes.push(Change {
+                    note_id: *note_id,
+                    kind: ChangeKind::Disappeared {
+                        last_status: state.status,
+                    },
+                });
+            }
+        }
+
+        changes.sort_by_key(|change| change.note_id);
+
+        DailyChanges {
+            date: next.date,
+            previous_date: Some(self.date),
+            changes,
+        }
+    }
+
+    /// The changes for an initial snapshot.
+    #[must_use]
+    pub fn initial_changes(&self) -> DailyChanges {
+        DailyChanges {
+            date: self.date,
+            previous_date: None,
+            changes: diff_notes(&BTreeMap::new(), &self.notes),
+        }
+    }
+}
+
+fn diff_notes(previous: &BTreeMap<u64, NoteState>, next: &BTreeMap<u64, NoteState>) -> Vec<Change> {
+    let mut changes = vec![];
+
+    for (note_id, state) in next {
+        let note_id = *note_id;
+
+        match previous.get(&note_id) {
+            None => {
+                changes.push(Change {
+                    note_id,
+                    kind: ChangeKind::Created {
+                        status: state.status,
+                    },
+                });
+
+                if let Some(status) = state.locked_status {
+                    changes.push(Change {
+                        note_id,
+                        kind: ChangeKind::Locked { status },
+                    });
+                }
+            }
+            Some(previous) => {
+                if previous.status != state.status {
+                    let kind = if state.status == Status::Helpful {
+                        ChangeKind::BecameHelpful {
+                            previous: previous.status,
+                        }
+                    } else if previous.status == Status::Helpful {
+                        ChangeKind::LostHelpful {
+                            current: state.status,
+                        }
+                    } else {
+                        ChangeKind::StatusChanged {
+                            previous: previous.status,
+                            current: state.status,
+                        }
+                    };
+
+                    changes.push(Change { note_id, kind });
+                }
+
+                if let (None, Some(status)) = (previous.locked_status, state.locked_status) {
+                    changes.push(Change {
+                        note_id,
+                        kind: ChangeKind::Locked { status },
+                    });
+                }
+            }
+        }
+    }
+
+    changes
+}
+
+/// A directory containing the latest snapshot and the change feed.
+pub struct Feed {
+    dir: PathBuf,
+}
+
+impl Feed {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// The most recent snapshot (if any).
+    pub fn snapshot(&self) -> Result<Option<Snapshot>, Error> {
+        let path = self.dir.join(SNAPSHOT_FILE_NAME);
+
+        if path.exists() {
+            let reader = BufReader::new(File::open(path)?);
+
+            Ok(Some(serde_json::from_reader(reader)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Diff a new snapshot against the most recent one, append the changes to the feed, and
+    /// replace the stored snapshot.
+    pub fn update(&self, snapshot: &Snapshot) -> Result<DailyChanges, Error> {
+        let changes = match self.snapshot()? {
+            Some(previous) if previous.date >= snapshot.date => {
+                return Err(Error::OutOfOrder {
+                    snapshot: previous.date,
+                    update: snapshot.date,
+                });
+            }
+            Some(previous) => previous.diff(snapshot),
+            None => snapshot.initial_changes(),
+        };
+
+        std::fs::create_dir_all(&self.dir)?;
+
+        let mut feed = BufWriter::new(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.dir.join(CHANGES_FILE_NAME))?,
+        );
+
+        serde_json::to_writer(&mut feed, &changes)?;
+        writeln!(feed)?;
+        feed.flush()?;
+        feed.into_inner()
+            .map_err(std::io::IntoInnerError::into_error)?
+            .sync_all()?;
+
+        // The snapshot is replaced after the feed is written, so an interrupted update can be
+        // repeated (at the cost of a duplicate entry in the feed).
+        let snapshot_path = self.dir.join(SNAPSHOT_FILE_NAME);
+        let snapshot_partial_path = self.dir.join(format!("{SNAPSHOT_FILE_NAME}.part"));
+
+        let mut snapshot_writer = BufWriter::new(File::create(&snapshot_partial_path)?);
+
+        serde_json::to_writer(&mut snapshot_writer, snapshot)?;
+        snapshot_writer.flush()?;
+        snapshot_writer
+            .into_inner()
+            .map_err(std::io::IntoInnerError::into_error)?
+            .sync_all()?;
+        std::fs::rename(snapshot_partial_path, snapshot_path)?;
+
+        Ok(changes)
+    }
+
+    /// All recorded daily changes, in the order they were added.
+    pub fn changes(&self) -> Result<Vec<DailyChanges>, Error> {
+        let path = self.dir.join(CHANGES_FILE_NAME);
+
+        if path.exists() {
+            BufReader::new(File::open(path)?)
+                .lines()
+                .filter(|line| line.as_ref().is_ok_and(|line| !line.is_empty()))
+                .map(|line| Ok(serde_json::from_str(&line?)?))
+                .collect()
+        } else {
+            Ok(vec![])
+        }
+    }
+}
+
+/// Statuses are stored as single characters.
+mod status_code {
+    use crate::model::Status;
+    use serde::{Deserialize, de::Unexpected};
+
+    pub const fn encode(status: Status) -> char {
+        match status {
+            Status::NeedsMoreRatings => 'M',
+            Status::NotHelpful => 'N',
+            Status::Helpful => 'H',
+        }
+    }
+
+    pub fn decode<E: serde::de::Error>(code: char) -> Result<Status, E> {
+        match code {
+            'M' => Ok(Status::NeedsMoreRatings),
+            'N' => Ok(Status::NotHelpful),
+            'H' => Ok(Status::Helpful),
+            other => Err(E::invalid_value(Unexpected::Char(other), &"M, N, or H")),
+        }
+    }
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Status, D::Error> {
+        decode(char::deserialize(deserializer)?)
+    }
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    pub fn serialize<S: serde::Serializer>(
+        value: &Status,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(encode(*value))
+    }
+}
+
+mod optional_status_code {
+    use crate::model::Status;
+    use serde::Deserialize;
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Status>, D::Error> {
+        Option::<char>::deserialize(deserializer)?
+            .map(super::status_code::decode)
+            .transpose()
+    }
+
+    #[allow(clippy::ref_option, clippy::trivially_copy_pass_by_ref)]
+    pub fn serialize<S: serde::Serializer>(
+        value: &Option<Status>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(status) => serializer.serialize_some(&super::status_code::encode(*status)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Change, ChangeKind, Error, Feed, Snapshot};
+    use crate::model::{
+        NoteStatusHistoryEntry, Status,
+        fixtures::{NOTE_STATUS_HISTORY, status_history_entry},
+    };
+    use chrono::NaiveDate;
+
+    #[test]
+    fn update_feed() {
+        let dir = tempfile::tempdir().unwrap();
+        let feed = Feed::new(dir.path());
+
+        let first_date = NaiveDate::from_ymd_opt(2025, 8, 27).unwrap();
+        let second_date = NaiveDate::from_ymd_opt(2025, 8, 28).unwrap();
+
+        let first = [
+            status_history_entry(1, "A", "NEEDS_MORE_RATINGS"),
+            status_history_entry(2, "A", "CURRENTLY_RATED_HELPFUL"),
+            status_history_entry(3, "A", "NEEDS_MORE_RATINGS"),
+            status_history_entry(4, "A", "NEEDS_MORE_RATINGS"),
+        ];
+        let second = [
+            status_history_entry(1, "A", "CURRENTLY_RATED_HELPFUL"),
+            status_history_entry(2, "A", "NEEDS_MORE_RATINGS"),
+            NoteStatusHistoryEntry {
+                locked_status: Some(Status::NotHelpful),
+                ..status_history_entry(3, "A", "CURRENTLY_RATED_NOT_HELPFUL")
+            },
+            status_history_entry(5, "A", "NEEDS_MORE_RATINGS"),
+        ];
+
+        let initial = feed.update(&Snapshot::new(first_date, &first)).unwrap();
+
+        assert_eq!(initial.previous_date, None);
+        assert_eq!(initial.changes.len(), 4);
+
+        let changes = feed.update(&Snapshot::new(second_date, &second)).unwrap();
+
+        assert_eq!(changes.previous_date, Some(first_date));
+        assert_eq!(
+            changes.changes,
+            vec![
+                Change {
+                    note_id: 1,
+                    kind: ChangeKind::BecameHelpful {
+                        previous: Status::NeedsMoreRatings
+                    }
+                },
+                Change {
+                    note_id: 2,
+                    kind: ChangeKind::LostHelpful {
+                        current: Status::NeedsMoreRatings
+                    }
+                },
+                Change {
+                    note_id: 3,
+                    kind: ChangeKind::StatusChanged {
+                        previous: Status::NeedsMoreRatings,
+                        current: Status::NotHelpful
+                    }
+                },
+                Change {
+                    note_id: 3,
+                    kind: ChangeKind::Locked {
+                        status: Status::NotHelpful
+                    }
+                },
+                Change {
+                    note_id: 4,
+                    kind: ChangeKind::Disappeared {
+                        last_status: Status::NeedsMoreRatings
+                    }
+                },
+                Change {
+                    note_id: 5,
+                    kind: ChangeKind::Created {
+                        status: Status::NeedsMoreRatings
+                    }
+                },
+            ]
+        );
+
+        assert_eq!(feed.changes().unwrap(), vec![initial, changes]);
+        assert_eq!(feed.snapshot().unwrap().unwrap().date, second_date);
+        assert!(matches!(
+            feed.update(&Snapshot::new(first_date, &first)),
+            Err(Error::OutOfOrder { .. })
+        ));
+
+        let snapshot = std::fs::read_to_string(dir.path().join(super::SNAPSHOT_FILE_NAME)).unwrap();
+
+        assert!(snapshot.contains(r#""3":{"s":"N","l":"N"}"#));
+    }
+
+    #[test]
+    fn stream_snapshot() {
+        let date = NaiveDate::from_ymd_opt(2025, 8, 27).unwrap();
+        let mut reader = crate::tsv::Reader::new(NOTE_STATUS_HISTORY.as_bytes()).unwrap();
+        let mut snapshot = Snapshot::empty(date);
+
+        while let Some(entry) = reader.read::<NoteStatusHistoryEntry<'_>>().unwrap() {
+            snapshot.insert(&entry);
+        }
+
+        let state = snapshot.notes[&1_354_864_556_552_712_194];
+
+        assert_eq!(snapshot.notes.len(), 1);
+        assert_eq!(state.status, Status::NotHelpful);
+        assert_eq!(state.locked_status, Some(Status::NotHelpful));
+    }
+}
@@ -0,0 +1,248 @@
+//! Joins public data rows with notes collected from the GraphQL API.
+use crate::model::{Classification, NoteEntry, NoteStatusHistoryEntry, Status};
+use birdsite::model::graphql::birdwatch::note::{self, Note, RatingStatus, model::Model};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+/// Everything known about a single note.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct JoinedNote<'a> {
+    pub note: Option<&'a NoteEntry<'a>>,
+    pub status: Option<&'a NoteStatusHistoryEntry<'a>>,
+    pub graphql: Option<&'a Note<'a>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Discrepancy {
+    /// The note is in the public data but was not collected from the GraphQL API.
+    PublicOnly { note_id: u64 },
+    /// The note was collected from the GraphQL API but was created after the public data cutoff (or
+    /// its creation time is unknown).
+    GraphqlOnly { note_id: u64 },
+    /// The note was created before the public data cutoff but is missing from the public data,
+    /// which does not include deleted notes.
+    Deleted { note_id: u64 },
+    Status {
+        note_id: u64,
+        public: Status,
+        graphql: Status,
+    },
+    DecidedBy {
+        note_id: u64,
+        public: Model,
+        graphql: Model,
+    },
+    Classification {
+        note_id: u64,
+        public: Classification,
+        graphql: Classification,
+    },
+    TweetId {
+        note_id: u64,
+        public: u64,
+        graphql: u64,
+    },
+}
+
+/// Notes from both sources, keyed by note ID.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Join<'a> {
+    notes: BTreeMap<u64, JoinedNote<'a>>,
+}
+
+impl<'a> Join<'a> {
+    pub fn add_note(&mut self, entry: &'a NoteEntry<'a>) {
+        self.notes.entry(entry.note_id).or_default().note = Some(entry);
+    }
+
+    pub fn add_status(&mut self, entry: &'a NoteStatusHistoryEntry<'a>) {
+        self.notes.entry(entry.note_id).or_default().status = Some(entry);
+    }
+
+    pub fn add_graphql(&mut self, note: &'a Note<'a>) {
+        self.notes.entry(note.id()).or_default().graphql = Some(note);
+    }
+
+    #[must_use]
+    pub fn get(&self, note_id: u64) -> Option<&JoinedNote<'a>> {
+        self.notes.get(&note_id)
+    }
+
+    /// All notes, ordered by ID.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &JoinedNote<'a>)> {
+        self.notes
+            .iter()
+            .map(|(note_id, joined)| (*note_id, joined))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.notes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    /// Compare the two sources.
+    ///
+    /// The cutoff should be the creation time of the public data (notes created after it cannot
+    /// appear there).
+    #[must_use]
+    pub fn discrepancies(&self, cutoff: DateTime<Utc>) -> Vec<Discrepancy> {
+        let mut discrepancies = vec![];
+
+        for (note_id, joined) in self.iter() {
+            let in_public = joined.note.is_some() || joined.status.is_some();
+
+            match (in_public, joined.graphql) {
+                (true, None) => discrepancies.push(Discrepancy::PublicOnly { note_id }),
+                (false, Some(graphql)) => {
+                    let created_at = graphql.metadata().and_then(|metadata| metadata.created_at);
+
+                    discrepancies.push(
+                        if created_at.is_some_and(|created_at| created_at < cutoff) {
+                            Discrepancy::Deleted { note_id }
+                        } else {
+                            Discrepancy::GraphqlOnly { note_id }
+                        },
+                    );
+                }
+                (true, Some(graphql)) => joined.compare(note_id, graphql, &mut discrepancies),
+                (false, None) => {}
+            }
+        }
+
+        discrepancies
+    }
+}
+
+impl JoinedNote<'_> {
+    fn compare(&self, note_id: u64, graphql: &Note<'_>, discrepancies: &mut Vec<Discrepancy>) {
+        let metadata = graphql.metadata();
+
+        if let Some((status, metadata)) = self.status.zip(metadata) {
+            if let Some(graphql_status) = metadata.rating_status.map(status_from_rating_status)
+                && graphql_status != status.current_status
+            {
+                discrepancies.push(Discrepancy::Status {
+                    note_id,
+                    public: status.current_status,
+                    graphql: graphql_status,
+                });
+            }
+
+            if let Some((public, graphql)) = status.current_decided_by.zip(metadata.decided_by)
+                && public != graphql
+            {
+                discrepancies.push(Discrepancy::DecidedBy {
+                    note_id,
+                    public,
+                    graphql,
+                });
+            }
+        }
+
+        if let Some((note, data)) = self.note.zip(graphql.data()) {
+            let graphql_classification = classification_from_graphql(data.classification);
+
+            if note.classification != graphql_classification {
+                discrepancies.push(Discrepancy::Classification {
+                    note_id,
+                    public: note.classification,
+                    graphql: graphql_classification,
+                });
+            }
+
+            if let Some((public, graphql)) = note.tweet_id.zip(data.tweet_id)
+                && public != graphql
+            {
+                discrepancies.push(Discrepancy::TweetId {
+                    note_id,
+                    public,
+                    graphql,
+                });
+            }
+        }
+    }
+}
+
+const fn status_from_rating_status(status: RatingStatus) -> Status {
+    match status {
+        RatingStatus::NeedsMoreRatings => Status::NeedsMoreRatings,
+        RatingStatus::CurrentlyRatedNotHelpful => Status::NotHelpful,
+        RatingStatus::CurrentlyRatedHelpful => Status::Helpful,
+    }
+}
+
+const fn classification_from_graphql(classification: note::Classification) -> Classification {
+    match classification {
+        note::Classification::NotMisleading => Classification::NotMisleading,
+        note::Classification::MisinformedOrPotentiallyMisleading => Classification::Misleading,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Discrepancy, Join};
+    use crate::model::{Status, fixtures::status_history_entry};
+    use birdsite::model::graphql::birdwatch::note::Note;
+    use chrono::DateTime;
+
+    #[derive(serde::Deserialize)]
+    struct BirdwatchNote<'a> {
+        #[serde(borrow)]
+        birdwatch_note_by_rest_id: Note<'a>,
+    }
+
+    #[test]
+    fn report_discrepancies() {
+        let graphql = include_str!("../../examples/graphql/birdwatch-notes-2025-08-28.ndjson")
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                serde_json::from_str::<BirdwatchNote<'_>>(line)
+                    .unwrap()
+                    .birdwatch_note_by_rest_id
+            })
+            .collect::<Vec<_>>();
+
+        let statuses = [
+            status_history_entry(1_959_814_924_454_297_777, "A", "CURRENTLY_RATED_HELPFUL"),
+            status_history_entry(1_774_610_362_052_096_018, "A", "NEEDS_MORE_RATINGS"),
+            status_history_entry(1, "A", "NEEDS_MORE_RATINGS"),
+        ];
+
+        let mut join = Join::default();
+
+        for note in &graphql {
+            join.add_graphql(note);
+        }
+
+        for status in &statuses {
+            join.add_status(status);
+        }
+
+        let cutoff = DateTime::from_timestamp(1_756_184_544, 0).unwrap();
+
+        assert_eq!(join.len(), 5);
+        assert_eq!(
+            join.discrepancies(cutoff),
+            vec![
+                Discrepancy::PublicOnly { note_id: 1 },
+                Discrepancy::GraphqlOnly {
+                    note_id: 1_752_179_556_151_337_409
+                },
+                Discrepancy::Deleted {
+                    note_id: 1_768_780_473_189_236_800
+                },
+                Discrepancy::Status {
+                    note_id: 1_959_814_924_454_297_777,
+                    public: Status::Helpful,
+                    graphql: Status::NeedsMoreRatings
+                },
+            ]
+        );
+    }
+}
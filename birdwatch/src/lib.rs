@@ -1,3 +1,12 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, rust_2018_idioms)]
+#![allow(clippy::missing_errors_doc)]
 #![forbid(unsafe_code)]
+#[cfg(feature = "parquet")]
+pub mod columnar;
+pub mod contributor;
+pub mod feed;
+pub mod join;
+pub mod mirror;
 pub mod model;
+pub mod scoring;
+pub mod tsv;
@@ -0,0 +1,496 @@
+//! A local mirror of the daily public data bundles.
+//!
+//! Each bundle is stored in a directory named for its date, using the paths computed by
+//! [`Bundle::downloads`], along with an `index.json` file listing the downloaded files.
+//!
+//! Files are downloaded to a `.part` file that is renamed when complete, so interrupted downloads
+//! can be resumed.
+use birdsite::model::graphql::birdwatch::manifest::{self, Bundle};
+use chrono::NaiveDate;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use url::Url;
+
+const INDEX_FILE_NAME: &str = "index.json";
+const PARTIAL_EXTENSION: &str = "part";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error<E> {
+    #[error("Manifest error")]
+    Manifest(#[from] manifest::Error),
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error")]
+    Json(#[from] serde_json::Error),
+    #[error("HTTP error")]
+    Http(#[source] E),
+    #[error("Unexpected file size")]
+    SizeMismatch {
+        path: PathBuf,
+        expected: u64,
+        found: u64,
+    },
+}
+
+/// The HTTP operations the mirror needs.
+pub trait Http {
+    type Error: std::error::Error + 'static;
+    type Body: Read;
+
+    /// The size of the resource (if the server provides it).
+    fn content_length(&self, url: &Url) -> Result<Option<u64>, Self::Error>;
+
+    /// The content of the resource, starting at the given byte offset.
+    ///
+    /// The body should be empty if the offset is not less than the size of the resource.
+    fn get(&self, url: &Url, offset: u64) -> Result<Self::Body, Self::Error>;
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Index {
+    pub date: NaiveDate,
+    pub files: Vec<IndexEntry>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct IndexEntry {
+    pub url: Url,
+    /// Relative to the bundle directory.
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+pub struct Mirror<H> {
+    root: PathBuf,
+    http: H,
+}
+
+impl<H: Http> Mirror<H> {
+    pub fn new<P: AsRef<Path>>(root: P, http: H) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            http,
+        }
+    }
+
+    #[must_use]
+    pub fn bundle_dir(&self, date: NaiveDate) -> PathBuf {
+        self.root.join(date.format("%Y-%m-%d").to_string())
+    }
+
+    /// The index for the bundle with the given date (if it has been mirrored).
+    pub fn index(&self, date: NaiveDate) -> Result<Option<Index>, Error<H::Error>> {
+        let path = self.bundle_dir(date).join(INDEX_FILE_NAME);
+
+        if path.exists() {
+            let reader = BufReader::new(File::open(path)?);
+
+            Ok(Some(serde_json::from_reader(reader)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Download any missing files for the bundle and write its index.
+    pub fn sync(&self, bundle: &Bundle) -> Result<Index, Error<H::Error>> {
+        let date = bundle.date()?;
+        let base = self.bundle_dir(date);
+        let existing = self.index(date)?;
+        let mut files = vec![];
+
+        for (url, path) in bundle.downloads(&base)? {
+            let indexed_size = existing.as_ref().and_then(|index| {
+                index
+                    .files
+                    .iter()
+                    .find(|entry| entry.url == url && base.join(&entry.path) == path)
+                    .map(|entry| entry.size)
+            });
+            let size = self.download(&url, &path, indexed_size)?;
+
+            files.push(IndexEntry {
+                url,
+                path: path
+                    .strip_prefix(&base)
+                    .map_or_else(|_| path.clone(), Path::to_path_buf),
+                size,
+            });
+        }
+
+        let index = Index { date, files };
+        let index_path = base.join(INDEX_FILE_NAME);
+        let index_partial_path = partial_path(&index_path);
+
+        let mut index_writer = BufWriter::new(File::create(&index_partial_path)?);
+
+        serde_json::to_writer_pretty(&mut index_writer, &index)?;
+        index_writer.flush()?;
+        index_writer
+            .into_inner()
+            .map_err(std::io::IntoInnerError::into_error)?
+            .sync_all()?;
+        std::fs::rename(index_partial_path, index_path)?;
+
+        Ok(index)
+    }
+
+    /// Download a single file (unless it already exists), returning its size.
+    ///
+    /// An existing file is only checked against the size reported by the server if it does not
+    /// have the size recorded in the index, and it is downloaded again if the sizes differ.
+    fn download(
+        &self,
+        url: &Url,
+        path: &Path,
+        indexed_size: Option<u64>,
+    ) -> Result<u64, Error<H::Error>> {
+        let existing_size = if path.exists() {
+            Some(std::fs::metadata(path)?.len())
+        } else {
+            None
+        };
+
+        if let Some(found) = existing_size.filter(|found| indexed_size == Some(*found)) {
+            return Ok(found);
+        }
+
+        let expected = self.http.content_length(url).map_err(Error::Http)?;
+
+        if let Some(found) = existing_size {
+            if expected.is_none_or(|expected| expected == found) {
+                return Ok(found);
+            }
+
+            // The resource has changed since the file was downloaded.
+            std::fs::remove_file(path)?;
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let partial_path = partial_path(path);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&partial_path)?;
+        let mut offset = file.metadata()?.len();
+
+        // The partial file can only be larger than the resource if the resource has changed.
+        if expected.is_some_and(|expected| offset > expected) {
+            file.set_len(0)?;
+            offset = 0;
+        }
+
+        if expected.is_none_or(|expected| offset < expected) {
+            let mut body = self.http.get(url, offset).map_err(Error::Http)?;
+
+            std::io::copy(&mut body, &mut file)?;
+        }
+
+        file.sync_all()?;
+
+        let found = file.metadata()?.len();
+        let size = check_size(&partial_path, expected, found)?;
+
+        std::fs::rename(partial_path, path)?;
+
+        Ok(size)
+    }
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+    let mut partial_path = path.as_os_str().to_os_string();
+    partial_path.push(".");
+    partial_path.push(PARTIAL_EXTENSION);
+    partial_path.into()
+}
+
+fn check_size<E: std::error::Error + 'static>(
+    path: &Path,
+    expected: Option<u64>,
+    found: u64,
+) -> Result<u64, Error<E>> {
+    match expected {
+        Some(expected) if expected != found => Err(Error::SizeMismatch {
+            path: path.to_path_buf(),
+            expected,
+            found,
+        }),
+        _ => Ok(found),
+    }
+}
+
+/// An [`Http`] implementation using a blocking `reqwest` client.
+#[cfg(feature = "reqwest")]
+pub mod client {
+    use ::reqwest::{
+        StatusCode,
+        blocking::Client,
+        header::{CONTENT_LENGTH, RANGE},
+    };
+    use std::io::Read;
+    use url::Url;
+
+    #[derive(thiserror::Error, Debug)]
+    pub enum Error {
+        #[error("HTTP client error")]
+        Reqwest(#[from] ::reqwest::Error),
+        #[error("I/O error")]
+        Io(#[from] std::io::Error),
+        #[error("Unexpected status")]
+        Status(StatusCode),
+    }
+
+    impl super::Http for Client {
+        type Error = Error;
+        type Body = Box<dyn Read>;
+
+        fn content_length(&self, url: &Url) -> Result<Option<u64>, Self::Error> {
+            let response = self.head(url.clone()).send()?.error_for_status()?;
+
+            // We read the header directly, since the body of a `HEAD` response is always empty.
+            Ok(response
+                .headers()
+                .get(CONTENT_LENGTH)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok()))
+        }
+
+        fn get(&self, url: &Url, offset: u64) -> Result<Self::Body, Self::Error> {
+            let mut request = Self::get(self, url.clone());
+
+            if offset > 0 {
+                request = request.header(RANGE, format!("bytes={offset}-"));
+            }
+
+            let mut response = request.send()?;
+
+            // The range starts at the end of the resource, so we already have all of it.
+            if offset > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+                return Ok(Box::new(std::io::empty()));
+            }
+
+            response = response.error_for_status()?;
+
+            match response.status() {
+                StatusCode::PARTIAL_CONTENT => Ok(Box::new(response)),
+                // The server has ignored the range, so we skip the content we already have.
+                StatusCode::OK => {
+                    std::io::copy(&mut (&mut response).take(offset), &mut std::io::sink())?;
+
+                    Ok(Box::new(response))
+                }
+                other => Err(Error::Status(other)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, Http, Mirror};
+    use birdsite::model::graphql::birdwatch::manifest::Bundle;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use url::Url;
+
+    #[derive(thiserror::Error, Debug)]
+    #[error("Not found")]
+    struct NotFound;
+
+    /// Serves fixed content, recording each size request and the offset of each content request.
+    #[derive(Default)]
+    struct LocalHttp {
+        content: HashMap<Url, Vec<u8>>,
+        heads: Mutex<Vec<Url>>,
+        requests: Mutex<Vec<(Url, u64)>>,
+    }
+
+    impl Http for LocalHttp {
+        type Error = NotFound;
+        type Body = std::io::Cursor<Vec<u8>>;
+
+        fn content_length(&self, url: &Url) -> Result<Option<u64>, Self::Error> {
+            self.heads.lock().unwrap().push(url.clone());
+
+            self.content
+                .get(url)
+                .map(|content| Some(content.len() as u64))
+                .ok_or(NotFound)
+        }
+
+        fn get(&self, url: &Url, offset: u64) -> Result<Self::Body, Self::Error> {
+            self.requests.lock().unwrap().push((url.clone(), offset));
+
+            let content = self.content.get(url).ok_or(NotFound)?;
+
+            Ok(std::io::Cursor::new(
+                content[usize::try_from(offset).unwrap()..].to_vec(),
+            ))
+        }
+    }
+
+    /// Reports one more byte than the wrapped server provides.
+    struct MisreportedSize(LocalHttp);
+
+    impl Http for MisreportedSize {
+        type Error = NotFound;
+        type Body = std::io::Cursor<Vec<u8>>;
+
+        fn content_length(&self, url: &Url) -> Result<Option<u64>, Self::Error> {
+            Ok(self.0.content_length(url)?.map(|size| size + 1))
+        }
+
+        fn get(&self, url: &Url, offset: u64) -> Result<Self::Body, Self::Error> {
+            self.0.get(url, offset)
+        }
+    }
+
+    fn bundle() -> Bundle {
+        let json = concat!(
+            r#"{"notes":{"created_at":1756184544431,"urls":["#,
+            r#""https://ton.twimg.com/birdwatch-public-data/2025/08/28/notes/notes-00000.zip"]},"#,
+            r#""ratings":{"created_at":1756184544431,"urls":["#,
+            r#""https://ton.twimg.com/birdwatch-public-data/2025/08/28/noteRatings/ratings-00000.zip"]},"#,
+            r#""note_status_history":{"created_at":1756184544431,"urls":["#,
+            r#""https://ton.twimg.com/birdwatch-public-data/2025/08/28/noteStatusHistory/noteStatusHistory-00000.zip"]},"#,
+            r#""user_enrollment":{"created_at":1756184544431,"urls":["#,
+            r#""https://ton.twimg.com/birdwatch-public-data/2025/08/28/userEnrollment/userEnrollment-00000.zip"]}}"#
+        );
+
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn local_http(bundle: &Bundle) -> LocalHttp {
+        let content = bundle
+            .downloads("")
+            .unwrap()
+            .into_iter()
+            .enumerate()
+            .map(|(i, (url, _))| (url, vec![u8::try_from(i).unwrap(); 100 + i]))
+            .collect();
+
+        LocalHttp {
+            content,
+            ..LocalHttp::default()
+        }
+    }
+
+    #[test]
+    fn sync_resumes_and_indexes() {
+        let root = tempfile::tempdir().unwrap();
+        let bundle = bundle();
+        let mirror = Mirror::new(root.path(), local_http(&bundle));
+        let date = bundle.date().unwrap();
+
+        // Simulate an interrupted download of the notes file.
+        let (notes_url, notes_path) =
+            bundle.notes.downloads(mirror.bundle_dir(date)).unwrap()[0].clone();
+        std::fs::create_dir_all(notes_path.parent().unwrap()).unwrap();
+        std::fs::write(super::partial_path(&notes_path), vec![0; 40]).unwrap();
+
+        assert_eq!(mirror.index(date).unwrap(), None);
+
+        let index = mirror.sync(&bundle).unwrap();
+
+        assert_eq!(index.files.len(), 4);
+        assert_eq!(index.files[0].size, 100);
+        assert_eq!(
+            index.files[0].path,
+            std::path::Path::new("notes/notes-00000.zip")
+        );
+        assert_eq!(std::fs::read(&notes_path).unwrap(), vec![0; 100]);
+        assert!(!super::partial_path(&notes_path).exists());
+        assert_eq!(mirror.index(date).unwrap(), Some(index));
+        assert!(
+            mirror
+                .http
+                .requests
+                .lock()
+                .unwrap()
+                .contains(&(notes_url, 40))
+        );
+
+        // Nothing is requested when the bundle is complete.
+        mirror.http.heads.lock().unwrap().clear();
+        mirror.http.requests.lock().unwrap().clear();
+        mirror.sync(&bundle).unwrap();
+
+        assert!(mirror.http.heads.lock().unwrap().is_empty());
+        assert!(mirror.http.requests.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn sync_restarts_oversized_partial_download() {
+        let root = tempfile::tempdir().unwrap();
+        let bundle = bundle();
+        let mirror = Mirror::new(root.path(), local_http(&bundle));
+        let date = bundle.date().unwrap();
+
+        let (notes_url, notes_path) =
+            bundle.notes.downloads(mirror.bundle_dir(date)).unwrap()[0].clone();
+        std::fs::create_dir_all(notes_path.parent().unwrap()).unwrap();
+        std::fs::write(super::partial_path(&notes_path), vec![1; 150]).unwrap();
+
+        let index = mirror.sync(&bundle).unwrap();
+
+        assert_eq!(index.files[0].size, 100);
+        assert_eq!(std::fs::read(&notes_path).unwrap(), vec![0; 100]);
+        assert!(
+            mirror
+                .http
+                .requests
+                .lock()
+                .unwrap()
+                .contains(&(notes_url, 0))
+        );
+    }
+
+    #[test]
+    fn sync_replaces_stale_files() {
+        let root = tempfile::tempdir().unwrap();
+        let bundle = bundle();
+        let mirror = Mirror::new(root.path(), local_http(&bundle));
+        let date = bundle.date().unwrap();
+
+        let (notes_url, notes_path) =
+            bundle.notes.downloads(mirror.bundle_dir(date)).unwrap()[0].clone();
+        std::fs::create_dir_all(notes_path.parent().unwrap()).unwrap();
+        std::fs::write(&notes_path, vec![1; 10]).unwrap();
+
+        let index = mirror.sync(&bundle).unwrap();
+
+        assert_eq!(index.files[0].size, 100);
+        assert_eq!(std::fs::read(&notes_path).unwrap(), vec![0; 100]);
+        assert!(
+            mirror
+                .http
+                .requests
+                .lock()
+                .unwrap()
+                .contains(&(notes_url, 0))
+        );
+    }
+
+    #[test]
+    fn sync_verifies_downloaded_sizes() {
+        let root = tempfile::tempdir().unwrap();
+        let bundle = bundle();
+        let mirror = Mirror::new(root.path(), MisreportedSize(local_http(&bundle)));
+        let date = bundle.date().unwrap();
+
+        assert!(matches!(
+            mirror.sync(&bundle),
+            Err(Error::SizeMismatch {
+                expected: 101,
+                found: 100,
+                ..
+            })
+        ));
+        assert_eq!(mirror.index(date).unwrap(), None);
+    }
+}
@@ -0,0 +1,144 @@
+//! Note requests ("bat signals").
+use super::NoteEntry;
+use bounded_static_derive_more::ToStatic;
+use chrono::{DateTime, TimeDelta, Utc, serde::ts_milliseconds};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+/// A row of the bat signals file.
+#[derive(Clone, Debug, Eq, PartialEq, ToStatic, serde::Deserialize, serde::Serialize)]
+pub struct BatSignal<'a> {
+    #[serde(rename = "userId", borrow)]
+    pub participant_id: Cow<'a, str>,
+    #[serde(rename = "tweetId")]
+    pub tweet_id: u64,
+    #[serde(rename = "createdAtMillis", with = "ts_milliseconds")]
+    pub created_at: DateTime<Utc>,
+    /// A link supporting the request (if the requester provided one).
+    #[serde(rename = "sourceLink", borrow, default)]
+    pub source_link: Option<Cow<'a, str>>,
+}
+
+/// Requests and notes for a single tweet.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TweetRequests {
+    pub request_count: usize,
+    pub first_requested_at: Option<DateTime<Utc>>,
+    pub last_requested_at: Option<DateTime<Utc>>,
+    /// Note IDs and creation times, in the order they were added.
+    pub notes: Vec<(u64, DateTime<Utc>)>,
+}
+
+impl TweetRequests {
+    #[must_use]
+    pub fn first_note_at(&self) -> Option<DateTime<Utc>> {
+        self.notes.iter().map(|(_, created_at)| *created_at).min()
+    }
+
+    /// The time from the first request to the first note written after it.
+    #[must_use]
+    pub fn time_to_first_note(&self) -> Option<TimeDelta> {
+        let first_requested_at = self.first_requested_at?;
+
+        self.notes
+            .iter()
+            .map(|(_, created_at)| *created_at)
+            .filter(|created_at| *created_at >= first_requested_at)
+            .min()
+            .map(|created_at| created_at - first_requested_at)
+    }
+}
+
+/// Correlates requests with the notes written for the requested tweets.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Requests {
+    tweets: BTreeMap<u64, TweetRequests>,
+}
+
+impl Requests {
+    pub fn add_request(&mut self, signal: &BatSignal<'_>) {
+        let requests = self.tweets.entry(signal.tweet_id).or_default();
+
+        requests.request_count += 1;
+        requests.first_requested_at = Some(
+            requests
+                .first_requested_at
+                .map_or(signal.created_at, |value| value.min(signal.created_at)),
+        );
+        requests.last_requested_at = Some(
+            requests
+                .last_requested_at
+                .map_or(signal.created_at, |value| value.max(signal.created_at)),
+        );
+    }
+
+    /// Notes are only recorded for tweets that have been requested.
+    pub fn add_note(&mut self, note: &NoteEntry<'_>) {
+        if let Some(requests) = note
+            .tweet_id
+            .and_then(|tweet_id| self.tweets.get_mut(&tweet_id))
+        {
+            requests.notes.push((note.note_id, note.created_at));
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, tweet_id: u64) -> Option<&TweetRequests> {
+        self.tweets.get(&tweet_id)
+    }
+
+    /// All requested tweets, ordered by ID.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &TweetRequests)> {
+        self.tweets
+            .iter()
+            .map(|(tweet_id, requests)| (*tweet_id, requests))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tweets.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tweets.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BatSignal, Requests};
+    use chrono::TimeDelta;
+
+    const BAT_SIGNALS: &str = concat!(
+        "userId\ttweetId\tcreatedAtMillis\tsourceLink\n",
+        "4874E27F86D8AC0AC3AB85FF754C25BE\t1700000000000000000\t1700000000000\t\n",
+        "F1B3F0D8EA3F1A8F1C3A6E7D5B4C2A10\t1700000000000000000\t1700000060000\t",
+        "https://example.com/\n"
+    );
+
+    #[test]
+    fn correlate_requests_and_notes() {
+        let mut reader = crate::tsv::Reader::new(BAT_SIGNALS.as_bytes()).unwrap();
+        let mut requests = Requests::default();
+
+        while let Some(signal) = reader.read::<BatSignal<'_>>().unwrap() {
+            requests.add_request(&signal);
+        }
+
+        let note = crate::model::NoteEntry {
+            created_at: chrono::DateTime::from_timestamp(1_700_003_600, 0).unwrap(),
+            tweet_id: Some(1_700_000_000_000_000_000),
+            ..crate::model::fixtures::note_entry(1, "A")
+        };
+
+        requests.add_note(&note);
+
+        let tweet = requests.get(1_700_000_000_000_000_000).unwrap();
+
+        assert_eq!(requests.len(), 1);
+        assert_eq!(tweet.request_count, 2);
+        assert_eq!(tweet.notes, vec![(1, note.created_at)]);
+        assert_eq!(tweet.time_to_first_note(), Some(TimeDelta::hours(1)));
+    }
+}
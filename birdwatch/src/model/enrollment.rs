@@ -0,0 +1,88 @@
+use super::field::{optional_modeling_group, optional_timestamp_millis};
+pub use birdsite::model::graphql::birdwatch::enrollment::EnrollmentState;
+use bounded_static_derive_more::ToStatic;
+use chrono::{DateTime, Utc, serde::ts_milliseconds};
+use std::borrow::Cow;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum ModelingPopulation {
+    #[serde(rename = "CORE")]
+    Core,
+    #[serde(rename = "EXPANSION")]
+    Expansion,
+    #[serde(rename = "EXPANSION_PLUS")]
+    ExpansionPlus,
+}
+
+/// A row of the user enrollment file.
+#[derive(Clone, Debug, Eq, PartialEq, ToStatic, serde::Deserialize, serde::Serialize)]
+pub struct UserEnrollment<'a> {
+    #[serde(rename = "participantId", borrow)]
+    pub participant_id: Cow<'a, str>,
+    #[serde(rename = "enrollmentState")]
+    pub enrollment_state: EnrollmentState,
+    #[serde(rename = "successfulRatingNeededToEarnIn")]
+    pub successful_rating_needed_to_earn_in: u32,
+    #[serde(rename = "timestampOfLastStateChange", with = "ts_milliseconds")]
+    pub last_state_change_at: DateTime<Utc>,
+    #[serde(rename = "timestampOfLastEarnOut", with = "optional_timestamp_millis")]
+    pub last_earn_out_at: Option<DateTime<Utc>>,
+    #[serde(rename = "modelingPopulation")]
+    pub modeling_population: ModelingPopulation,
+    #[serde(rename = "modelingGroup", with = "optional_modeling_group")]
+    pub modeling_group: Option<u8>,
+    #[serde(rename = "numberOfTimesEarnedOut")]
+    pub number_of_times_earned_out: u32,
+}
+
+/// A change in a contributor's enrollment state between two bundles.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateChange<'a> {
+    pub participant_id: Cow<'a, str>,
+    pub old: EnrollmentState,
+    pub new: EnrollmentState,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl<'a> UserEnrollment<'a> {
+    /// The state change since an earlier row for the same contributor (if any).
+    #[must_use]
+    pub fn state_change_since(&self, previous: &Self) -> Option<StateChange<'a>> {
+        (self.participant_id == previous.participant_id
+            && self.enrollment_state != previous.enrollment_state)
+            .then(|| StateChange {
+                participant_id: self.participant_id.clone(),
+                old: previous.enrollment_state,
+                new: self.enrollment_state,
+                changed_at: self.last_state_change_at,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EnrollmentState, ModelingPopulation, UserEnrollment};
+    use crate::model::fixtures::USER_ENROLLMENT;
+
+    #[test]
+    fn read_user_enrollment() {
+        let mut reader = crate::tsv::Reader::new(USER_ENROLLMENT.as_bytes()).unwrap();
+        let mut rows = vec![];
+
+        while let Some(row) = reader.read::<UserEnrollment<'_>>().unwrap() {
+            rows.push(bounded_static::IntoBoundedStatic::into_static(row));
+        }
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].modeling_group, Some(13));
+        assert_eq!(rows[2].modeling_group, None);
+        assert_eq!(rows[2].modeling_population, ModelingPopulation::Expansion);
+
+        let change = rows[1].state_change_since(&rows[0]).unwrap();
+
+        assert_eq!(change.old, EnrollmentState::EarnedIn);
+        assert_eq!(change.new, EnrollmentState::AtRisk);
+        assert!(change.new.can_write());
+        assert!(rows[2].state_change_since(&rows[0]).is_none());
+    }
+}
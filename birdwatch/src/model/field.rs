@@ -0,0 +1,113 @@
+//! Column encodings used in the public data files.
+
+/// Boolean columns are encoded as `0` or `1`.
+pub mod flag {
+    use serde::{Deserialize, de::Unexpected};
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<bool, D::Error> {
+        match u8::deserialize(deserializer)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(serde::de::Error::invalid_value(
+                Unexpected::Unsigned(other.into()),
+                &"0 or 1",
+            )),
+        }
+    }
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    pub fn serialize<S: serde::Serializer>(value: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(u8::from(*value))
+    }
+}
+
+/// Tweet ID columns use `-1` for missing values.
+pub mod optional_tweet_id {
+    use serde::{Deserialize, de::Unexpected};
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<u64>, D::Error> {
+        match i64::deserialize(deserializer)? {
+            -1 => Ok(None),
+            other => u64::try_from(other).map(Some).map_err(|_| {
+                serde::de::Error::invalid_value(Unexpected::Signed(other), &"tweet ID or -1")
+            }),
+        }
+    }
+
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: serde::Serializer>(
+        value: &Option<u64>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_u64(*value),
+            None => serializer.serialize_i64(-1),
+        }
+    }
+}
+
+/// Timestamp columns use `-1` (or occasionally an empty value) for missing values.
+pub mod optional_timestamp_millis {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, de::Unexpected};
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        match Option::<i64>::deserialize(deserializer)? {
+            None | Some(-1) => Ok(None),
+            Some(other) => DateTime::from_timestamp_millis(other)
+                .map(Some)
+                .ok_or_else(|| {
+                    serde::de::Error::invalid_value(Unexpected::Signed(other), &"timestamp or -1")
+                }),
+        }
+    }
+
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: serde::Serializer>(
+        value: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(value.map_or(-1, |value| value.timestamp_millis()))
+    }
+}
+
+/// Modeling groups are integers encoded as floats (for example `13.0`), and may be empty.
+pub mod optional_modeling_group {
+    use serde::{Deserialize, de::Unexpected};
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<u8>, D::Error> {
+        Option::<f64>::deserialize(deserializer)?
+            .map(|value| {
+                if value.fract() == 0.0 && (0.0..=f64::from(u8::MAX)).contains(&value) {
+                    // Safe because we've just checked the range and that there is no fractional part.
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    Ok(value as u8)
+                } else {
+                    Err(serde::de::Error::invalid_value(
+                        Unexpected::Float(value),
+                        &"modeling group",
+                    ))
+                }
+            })
+            .transpose()
+    }
+
+    #[allow(clippy::ref_option, clippy::trivially_copy_pass_by_ref)]
+    pub fn serialize<S: serde::Serializer>(
+        value: &Option<u8>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_f64(f64::from(*value)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
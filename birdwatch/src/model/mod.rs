@@ -0,0 +1,495 @@
+use birdsite::model::graphql::birdwatch::note::model::Model;
+use bounded_static_derive_more::ToStatic;
+use chrono::{DateTime, Utc, serde::ts_milliseconds};
+use std::borrow::Cow;
+
+pub mod bat_signal;
+pub mod enrollment;
+mod field;
+pub mod rating;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Status {
+    #[serde(rename = "NEEDS_MORE_RATINGS")]
+    NeedsMoreRatings,
+    #[serde(rename = "CURRENTLY_RATED_NOT_HELPFUL")]
+    NotHelpful,
+    #[serde(rename = "CURRENTLY_RATED_HELPFUL")]
+    Helpful,
+}
+
+impl Status {
+    #[must_use]
+    pub const fn is_helpful(self) -> Option<bool> {
+        match self {
+            Self::NeedsMoreRatings => None,
+            Self::NotHelpful => Some(false),
+            Self::Helpful => Some(true),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Classification {
+    #[serde(rename = "NOT_MISLEADING")]
+    NotMisleading,
+    #[serde(rename = "MISINFORMED_OR_POTENTIALLY_MISLEADING")]
+    Misleading,
+    #[serde(rename = "")]
+    Empty,
+}
+
+/// A row of the note status history files.
+#[derive(Clone, Debug, Eq, PartialEq, ToStatic, serde::Deserialize, serde::Serialize)]
+pub struct NoteStatusHistoryEntry<'a> {
+    #[serde(rename = "noteId")]
+    pub note_id: u64,
+    #[serde(rename = "noteAuthorParticipantId", borrow)]
+    pub participant_id: Cow<'a, str>,
+    #[serde(rename = "createdAtMillis", with = "ts_milliseconds")]
+    pub created_at: DateTime<Utc>,
+    #[serde(
+        rename = "timestampMillisOfFirstNonNMRStatus",
+        with = "field::optional_timestamp_millis",
+        default
+    )]
+    pub first_non_nmr_status_at: Option<DateTime<Utc>>,
+    #[serde(rename = "firstNonNMRStatus", default)]
+    pub first_non_nmr_status: Option<Status>,
+    #[serde(
+        rename = "timestampMillisOfCurrentStatus",
+        with = "field::optional_timestamp_millis",
+        default
+    )]
+    pub current_status_at: Option<DateTime<Utc>>,
+    #[serde(rename = "currentStatus")]
+    pub current_status: Status,
+    #[serde(
+        rename = "timestampMillisOfLatestNonNMRStatus",
+        with = "field::optional_timestamp_millis",
+        default
+    )]
+    pub most_recent_non_nmr_status_at: Option<DateTime<Utc>>,
+    #[serde(rename = "mostRecentNonNMRStatus", default)]
+    pub most_recent_non_nmr_status: Option<Status>,
+    #[serde(
+        rename = "timestampMillisOfStatusLock",
+        with = "field::optional_timestamp_millis",
+        default
+    )]
+    pub status_locked_at: Option<DateTime<Utc>>,
+    #[serde(rename = "lockedStatus", default)]
+    pub locked_status: Option<Status>,
+    #[serde(
+        rename = "timestampMillisOfRetroLock",
+        with = "field::optional_timestamp_millis",
+        default
+    )]
+    pub retro_locked_at: Option<DateTime<Utc>>,
+    #[serde(rename = "currentCoreStatus", default)]
+    pub current_core_status: Option<Status>,
+    #[serde(rename = "currentExpansionStatus", default)]
+    pub current_expansion_status: Option<Status>,
+    #[serde(rename = "currentGroupStatus", default)]
+    pub current_group_status: Option<Status>,
+    #[serde(rename = "currentMultiGroupStatus", default)]
+    pub current_multi_group_status: Option<Status>,
+    /// The scoring model that decided the first non-NMR status.
+    #[serde(rename = "firstDecidedBy", default)]
+    pub first_decided_by: Option<Model>,
+    /// The scoring model that decided the current status.
+    #[serde(rename = "currentDecidedBy", default)]
+    pub current_decided_by: Option<Model>,
+    #[serde(
+        rename = "currentModelingGroup",
+        with = "field::optional_modeling_group",
+        default
+    )]
+    pub current_modeling_group: Option<u8>,
+    #[serde(
+        rename = "currentModelingMultiGroup",
+        with = "field::optional_modeling_group",
+        default
+    )]
+    pub current_modeling_multi_group: Option<u8>,
+    #[serde(
+        rename = "timestampMillisOfMostRecentStatusChange",
+        with = "field::optional_timestamp_millis",
+        default
+    )]
+    pub most_recent_status_change_at: Option<DateTime<Utc>>,
+    #[serde(
+        rename = "timestampMillisOfNmrDueToMinStableCrhTime",
+        with = "field::optional_timestamp_millis",
+        default
+    )]
+    pub nmr_due_to_min_stable_crh_time_at: Option<DateTime<Utc>>,
+    #[serde(
+        rename = "timestampMillisOfFirstNmrDueToMinStableCrhTime",
+        with = "field::optional_timestamp_millis",
+        default
+    )]
+    pub first_nmr_due_to_min_stable_crh_time_at: Option<DateTime<Utc>>,
+    /// The time of the scoring run that produced this row (truncated to the minute).
+    #[serde(
+        rename = "timestampMinuteOfFinalScoringOutput",
+        with = "field::optional_timestamp_millis",
+        default
+    )]
+    pub final_scoring_output_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum StatusTransitionKind {
+    Created,
+    FirstNonNmr,
+    MostRecentNonNmr,
+    Current,
+    Locked,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StatusTransition {
+    pub timestamp: DateTime<Utc>,
+    pub status: Status,
+    pub kind: StatusTransitionKind,
+}
+
+impl NoteStatusHistoryEntry<'_> {
+    /// The status transitions recorded in this row, ordered by time.
+    ///
+    /// Every note starts as needing more ratings when it is created, and transitions without a
+    /// timestamp are omitted.
+    #[must_use]
+    pub fn status_timeline(&self) -> Vec<StatusTransition> {
+        let mut transitions = [
+            Some((
+                self.created_at,
+                Status::NeedsMoreRatings,
+                StatusTransitionKind::Created,
+            )),
+            self.first_non_nmr_status_at
+                .zip(self.first_non_nmr_status)
+                .map(|(timestamp, status)| (timestamp, status, StatusTransitionKind::FirstNonNmr)),
+            self.most_recent_non_nmr_status_at
+                .zip(self.most_recent_non_nmr_status)
+                .map(|(timestamp, status)| {
+                    (timestamp, status, StatusTransitionKind::MostRecentNonNmr)
+                }),
+            self.current_status_at.map(|timestamp| {
+                (
+                    timestamp,
+                    self.current_status,
+                    StatusTransitionKind::Current,
+                )
+            }),
+            self.status_locked_at
+                .zip(self.locked_status)
+                .map(|(timestamp, status)| (timestamp, status, StatusTransitionKind::Locked)),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|(timestamp, status, kind)| StatusTransition {
+            timestamp,
+            status,
+            kind,
+        })
+        .collect::<Vec<_>>();
+
+        transitions.sort_by_key(|transition| (transition.timestamp, transition.kind));
+
+        // The same transition is often recorded in several columns.
+        transitions.dedup_by(|next, previous| {
+            next.timestamp == previous.timestamp && next.status == previous.status
+        });
+
+        transitions
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Believable {
+    #[serde(rename = "BELIEVABLE_BY_FEW")]
+    ByFew,
+    #[serde(rename = "BELIEVABLE_BY_MANY")]
+    ByMany,
+    #[serde(rename = "")]
+    Empty,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Harmful {
+    #[serde(rename = "LITTLE_HARM")]
+    LittleHarm,
+    #[serde(rename = "CONSIDERABLE_HARM")]
+    ConsiderableHarm,
+    #[serde(rename = "")]
+    Empty,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum ValidationDifficulty {
+    #[serde(rename = "EASY")]
+    Easy,
+    #[serde(rename = "CHALLENGING")]
+    Challenging,
+    #[serde(rename = "")]
+    Empty,
+}
+
+/// A row of the notes files.
+// The reason flags mirror the columns of the public data schema.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug, Eq, PartialEq, ToStatic, serde::Deserialize, serde::Serialize)]
+pub struct NoteEntry<'a> {
+    #[serde(rename = "noteId")]
+    pub note_id: u64,
+    #[serde(rename = "noteAuthorParticipantId", borrow)]
+    pub participant_id: Cow<'a, str>,
+    #[serde(rename = "createdAtMillis", with = "ts_milliseconds")]
+    pub created_at: DateTime<Utc>,
+    /// Missing for a small number of notes (where the file has `-1`).
+    #[serde(rename = "tweetId", with = "field::optional_tweet_id")]
+    pub tweet_id: Option<u64>,
+    #[serde(rename = "classification")]
+    pub classification: Classification,
+    pub believable: Believable,
+    pub harmful: Harmful,
+    #[serde(rename = "validationDifficulty")]
+    pub validation_difficulty: ValidationDifficulty,
+    #[serde(rename = "misleadingOther", with = "field::flag")]
+    pub misleading_other: bool,
+    #[serde(rename = "misleadingFactualError", with = "field::flag")]
+    pub misleading_factual_error: bool,
+    #[serde(rename = "misleadingManipulatedMedia", with = "field::flag")]
+    pub misleading_manipulated_media: bool,
+    #[serde(rename = "misleadingOutdatedInformation", with = "field::flag")]
+    pub misleading_outdated_information: bool,
+    #[serde(rename = "misleadingMissingImportantContext", with = "field::flag")]
+    pub misleading_missing_important_context: bool,
+    #[serde(rename = "misleadingUnverifiedClaimAsFact", with = "field::flag")]
+    pub misleading_unverified_claim_as_fact: bool,
+    #[serde(rename = "misleadingSatire", with = "field::flag")]
+    pub misleading_satire: bool,
+    #[serde(rename = "notMisleadingOther", with = "field::flag")]
+    pub not_misleading_other: bool,
+    #[serde(rename = "notMisleadingFactuallyCorrect", with = "field::flag")]
+    pub not_misleading_factually_correct: bool,
+    #[serde(
+        rename = "notMisleadingOutdatedButNotWhenWritten",
+        with = "field::flag"
+    )]
+    pub not_misleading_outdated_but_not_when_written: bool,
+    #[serde(rename = "notMisleadingClearlySatire", with = "field::flag")]
+    pub not_misleading_clearly_satire: bool,
+    #[serde(rename = "notMisleadingPersonalOpinion", with = "field::flag")]
+    pub not_misleading_personal_opinion: bool,
+    #[serde(rename = "trustworthySources", with = "field::flag")]
+    pub trustworthy_sources: bool,
+    #[serde(borrow)]
+    pub summary: Cow<'a, str>,
+    #[serde(rename = "isMediaNote", with = "field::flag")]
+    pub is_media_note: bool,
+}
+
+/// Rows for tests.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::{NoteEntry, NoteStatusHistoryEntry, rating::RatingEntry};
+    use bounded_static::IntoBoundedStatic;
+
+    /// Two notes, one with a quoted summary and one with a tweet ID.
+    pub const NOTES: &str = concat!(
+        "noteId\tnoteAuthorParticipantId\tcreatedAtMillis\ttweetId\tclassification\t",
+        "believable\tharmful\tvalidationDifficulty\tmisleadingOther\tmisleadingFactualError\t",
+        "misleadingManipulatedMedia\tmisleadingOutdatedInformation\t",
+        "misleadingMissingImportantContext\tmisleadingUnverifiedClaimAsFact\tmisleadingSatire\t",
+        "notMisleadingOther\tnotMisleadingFactuallyCorrect\t",
+        "notMisleadingOutdatedButNotWhenWritten\tnotMisleadingClearlySatire\t",
+        "notMisleadingPersonalOpinion\ttrustworthySources\tsummary\tisMediaNote\n",
+        "1354864556552712194\t4874E27F86D8AC0AC3AB85FF754C25BE\t1611767358984\t-1\t",
+        "MISINFORMED_OR_POTENTIALLY_MISLEADING\tBELIEVABLE_BY_MANY\tCONSIDERABLE_HARM\tEASY\t",
+        "0\t1\t0\t0\t1\t0\t0\t0\t0\t0\t0\t0\t1\t",
+        "\"The claim is \"\"false\"\";\tsee https://example.com/\"\t0\n",
+        "1700000000000000000\t4874E27F86D8AC0AC3AB85FF754C25BE\t1700000000000\t",
+        "1699999999999999999\tNOT_MISLEADING\t\t\t\t0\t0\t0\t0\t0\t0\t0\t0\t1\t0\t0\t0\t",
+        "1\tThis is accurate.\t1\n"
+    );
+
+    /// A locked note with a first and current decided-by model.
+    pub const NOTE_STATUS_HISTORY: &str = concat!(
+        "noteId\tnoteAuthorParticipantId\tcreatedAtMillis\ttimestampMillisOfFirstNonNMRStatus\t",
+        "firstNonNMRStatus\ttimestampMillisOfCurrentStatus\tcurrentStatus\t",
+        "timestampMillisOfLatestNonNMRStatus\tmostRecentNonNMRStatus\t",
+        "timestampMillisOfStatusLock\tlockedStatus\ttimestampMillisOfRetroLock\t",
+        "currentCoreStatus\tcurrentExpansionStatus\tcurrentGroupStatus\tcurrentDecidedBy\t",
+        "currentModelingGroup\ttimestampMillisOfMostRecentStatusChange\tfirstDecidedBy\n",
+        "1354864556552712194\t4874E27F86D8AC0AC3AB85FF754C25BE\t1611767358984\t",
+        "1611800000000\tCURRENTLY_RATED_HELPFUL\t1611900000000\tCURRENTLY_RATED_NOT_HELPFUL\t",
+        "1611900000000\tCURRENTLY_RATED_NOT_HELPFUL\t1613000000000\tCURRENTLY_RATED_NOT_HELPFUL\t",
+        "-1\tCURRENTLY_RATED_NOT_HELPFUL\t\t\tCoreModel (v1.1)\t0.0\t1611900000000\t",
+        "ExpansionModel (v1.1)\n"
+    );
+
+    /// Two ratings of the same note, using version 1 and version 2 helpfulness fields.
+    pub const RATINGS: &str = concat!(
+        "noteId\traterParticipantId\tcreatedAtMillis\tversion\tagree\tdisagree\thelpful\t",
+        "notHelpful\thelpfulnessLevel\thelpfulOther\thelpfulInformative\thelpfulClear\t",
+        "helpfulEmpathetic\thelpfulGoodSources\thelpfulUniqueContext\thelpfulAddressesClaim\t",
+        "helpfulImportantContext\thelpfulUnbiasedLanguage\tnotHelpfulOther\tnotHelpfulIncorrect\t",
+        "notHelpfulSourcesMissingOrUnreliable\tnotHelpfulOpinionSpeculationOrBias\t",
+        "notHelpfulMissingKeyPoints\tnotHelpfulOutdated\tnotHelpfulHardToUnderstand\t",
+        "notHelpfulArgumentativeOrBiased\tnotHelpfulOffTopic\tnotHelpfulSpamHarassmentOrAbuse\t",
+        "notHelpfulIrrelevantSources\tnotHelpfulOpinionSpeculation\tnotHelpfulNoteNotNeeded\t",
+        "ratedOnTweetId\n",
+        "1400247230330667008\t4874E27F86D8AC0AC3AB85FF754C25BE\t1622700382000\t1\t0\t0\t1\t0\t",
+        "\t0\t1\t1\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t-1\n",
+        "1400247230330667008\tF1B3F0D8EA3F1A8F1C3A6E7D5B4C2A10\t1700000000000\t2\t0\t0\t0\t0\t",
+        "NOT_HELPFUL\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t1\t1\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t",
+        "1400247230330667009\n"
+    );
+
+    /// Enrollment rows for one participant in consecutive bundles, and a new user with no
+    /// modeling group who has earned out once.
+    pub const USER_ENROLLMENT: &str = concat!(
+        "participantId\tenrollmentState\tsuccessfulRatingNeededToEarnIn\t",
+        "timestampOfLastStateChange\ttimestampOfLastEarnOut\tmodelingPopulation\tmodelingGroup\t",
+        "numberOfTimesEarnedOut\n",
+        "A\tearnedIn\t5\t1700000000000\t-1\tCORE\t13.0\t0\n",
+        "A\tatRisk\t5\t1700086400000\t-1\tCORE\t13.0\t0\n",
+        "B\tnewUser\t5\t1700000000000\t1690000000000\tEXPANSION\t\t1\n"
+    );
+
+    /// A note with no tweet, classification, reasons, or summary.
+    pub fn note_entry(note_id: u64, participant_id: &str) -> NoteEntry<'static> {
+        serde_json::from_str::<NoteEntry<'_>>(&format!(
+            concat!(
+                r#"{{"noteId":{},"noteAuthorParticipantId":"{}","createdAtMillis":0,"#,
+                r#""tweetId":-1,"classification":"","believable":"","harmful":"","#,
+                r#""validationDifficulty":"","misleadingOther":0,"misleadingFactualError":0,"#,
+                r#""misleadingManipulatedMedia":0,"misleadingOutdatedInformation":0,"#,
+                r#""misleadingMissingImportantContext":0,"misleadingUnverifiedClaimAsFact":0,"#,
+                r#""misleadingSatire":0,"notMisleadingOther":0,"#,
+                r#""notMisleadingFactuallyCorrect":0,"notMisleadingOutdatedButNotWhenWritten":0,"#,
+                r#""notMisleadingClearlySatire":0,"notMisleadingPersonalOpinion":0,"#,
+                r#""trustworthySources":0,"summary":"","isMediaNote":0}}"#
+            ),
+            note_id, participant_id
+        ))
+        .map(IntoBoundedStatic::into_static)
+        .unwrap()
+    }
+
+    /// A version 2 rating row with no helpfulness level and no flags set.
+    pub fn rating_entry(note_id: u64, participant_id: &str) -> RatingEntry<'static> {
+        serde_json::from_str::<RatingEntry<'_>>(&format!(
+            concat!(
+                r#"{{"noteId":{},"raterParticipantId":"{}","createdAtMillis":0,"version":2,"#,
+                r#""agree":0,"disagree":0,"helpful":0,"notHelpful":0,"helpfulnessLevel":"","#,
+                r#""helpfulOther":0,"helpfulInformative":0,"helpfulClear":0,"#,
+                r#""helpfulEmpathetic":0,"helpfulGoodSources":0,"helpfulUniqueContext":0,"#,
+                r#""helpfulAddressesClaim":0,"helpfulImportantContext":0,"#,
+                r#""helpfulUnbiasedLanguage":0,"notHelpfulOther":0,"notHelpfulIncorrect":0,"#,
+                r#""notHelpfulSourcesMissingOrUnreliable":0,"#,
+                r#""notHelpfulOpinionSpeculationOrBias":0,"notHelpfulMissingKeyPoints":0,"#,
+                r#""notHelpfulOutdated":0,"notHelpfulHardToUnderstand":0,"#,
+                r#""notHelpfulArgumentativeOrBiased":0,"notHelpfulOffTopic":0,"#,
+                r#""notHelpfulSpamHarassmentOrAbuse":0,"notHelpfulIrrelevantSources":0,"#,
+                r#""notHelpfulOpinionSpeculation":0,"notHelpfulNoteNotNeeded":0,"#,
+                r#""ratedOnTweetId":-1}}"#
+            ),
+            note_id, participant_id
+        ))
+        .map(IntoBoundedStatic::into_static)
+        .unwrap()
+    }
+
+    /// A status history row with only the required fields.
+    pub fn status_history_entry(
+        note_id: u64,
+        participant_id: &str,
+        current_status: &str,
+    ) -> NoteStatusHistoryEntry<'static> {
+        serde_json::from_str::<NoteStatusHistoryEntry<'_>>(&format!(
+            r#"{{"noteId":{note_id},"noteAuthorParticipantId":"{participant_id}","createdAtMillis":0,"currentStatus":"{current_status}"}}"#
+        ))
+        .map(IntoBoundedStatic::into_static)
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::{NOTE_STATUS_HISTORY, NOTES};
+    use super::*;
+
+    #[test]
+    fn borrows_participant_id() {
+        // Regression: without `#[serde(borrow)]`, serde's blanket `Cow` impl always produces
+        // `Cow::Owned`, heap-allocating every participant id.
+        let json = concat!(
+            r#"{"noteId":1,"noteAuthorParticipantId":"4874E27F86D8AC0AC3AB85FF754C25BE","#,
+            r#""createdAtMillis":1245946721000,"currentStatus":"CURRENTLY_RATED_HELPFUL"}"#
+        );
+
+        let entry = serde_json::from_str::<NoteStatusHistoryEntry<'_>>(json).unwrap();
+
+        assert!(matches!(entry.participant_id, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn read_notes() {
+        let mut reader = crate::tsv::Reader::new(NOTES.as_bytes()).unwrap();
+
+        let first = reader.read::<NoteEntry<'_>>().unwrap().unwrap();
+
+        assert_eq!(first.tweet_id, None);
+        assert_eq!(first.classification, Classification::Misleading);
+        assert_eq!(first.harmful, Harmful::ConsiderableHarm);
+        assert!(first.misleading_factual_error);
+        assert!(first.trustworthy_sources);
+        assert_eq!(
+            first.summary,
+            "The claim is \"false\";\tsee https://example.com/"
+        );
+        assert!(!first.is_media_note);
+
+        let second = reader.read::<NoteEntry<'_>>().unwrap().unwrap();
+
+        assert_eq!(second.tweet_id, Some(1_699_999_999_999_999_999));
+        assert_eq!(second.believable, Believable::Empty);
+        assert!(matches!(second.summary, Cow::Borrowed(_)));
+        assert!(second.is_media_note);
+    }
+
+    #[test]
+    fn note_status_timeline() {
+        let mut reader = crate::tsv::Reader::new(NOTE_STATUS_HISTORY.as_bytes()).unwrap();
+
+        let entry = reader
+            .read::<NoteStatusHistoryEntry<'_>>()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(entry.retro_locked_at, None);
+        assert_eq!(entry.current_expansion_status, None);
+        assert_eq!(entry.first_decided_by, Some(Model::ExpansionModel));
+        assert_eq!(entry.current_decided_by, Some(Model::CoreModel));
+        assert_eq!(entry.current_modeling_group, Some(0));
+
+        let timeline = entry.status_timeline();
+
+        assert_eq!(
+            timeline
+                .iter()
+                .map(|transition| (transition.status, transition.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                (Status::NeedsMoreRatings, StatusTransitionKind::Created),
+                (Status::Helpful, StatusTransitionKind::FirstNonNmr),
+                (Status::NotHelpful, StatusTransitionKind::MostRecentNonNmr),
+                (Status::NotHelpful, StatusTransitionKind::Locked),
+            ]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use super::field::{flag, optional_tweet_id};
+use bounded_static_derive_more::ToStatic;
+use chrono::{DateTime, Utc, serde::ts_milliseconds};
+use std::borrow::Cow;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum HelpfulnessLevel {
+    #[serde(rename = "HELPFUL")]
+    Helpful,
+    #[serde(rename = "SOMEWHAT_HELPFUL")]
+    SomewhatHelpful,
+    #[serde(rename = "NOT_HELPFUL")]
+    NotHelpful,
+    /// Version 1 ratings only have the `helpful` and `notHelpful` flags.
+    #[serde(rename = "")]
+    Empty,
+}
+
+/// A row of the ratings files.
+// The reason flags mirror the columns of the public data schema.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug, Eq, PartialEq, ToStatic, serde::Deserialize, serde::Serialize)]
+pub struct RatingEntry<'a> {
+    #[serde(rename = "noteId")]
+    pub note_id: u64,
+    #[serde(rename = "raterParticipantId", borrow)]
+    pub participant_id: Cow<'a, str>,
+    #[serde(rename = "createdAtMillis", with = "ts_milliseconds")]
+    pub created_at: DateTime<Utc>,
+    pub version: u8,
+    #[serde(with = "flag")]
+    pub agree: bool,
+    #[serde(with = "flag")]
+    pub disagree: bool,
+    #[serde(with = "flag")]
+    pub helpful: bool,
+    #[serde(rename = "notHelpful", with = "flag")]
+    pub not_helpful: bool,
+    #[serde(rename = "helpfulnessLevel")]
+    pub helpfulness_level: HelpfulnessLevel,
+    #[serde(rename = "helpfulOther", with = "flag")]
+    pub helpful_other: bool,
+    #[serde(rename = "helpfulInformative", with = "flag")]
+    pub helpful_informative: bool,
+    #[serde(rename = "helpfulClear", with = "flag")]
+    pub helpful_clear: bool,
+    #[serde(rename = "helpfulEmpathetic", with = "flag")]
+    pub helpful_empathetic: bool,
+    #[serde(rename = "helpfulGoodSources", with = "flag")]
+    pub helpful_good_sources: bool,
+    #[serde(rename = "helpfulUniqueContext", with = "flag")]
+    pub helpful_unique_context: bool,
+    #[serde(rename = "helpfulAddressesClaim", with = "flag")]
+    pub helpful_addresses_claim: bool,
+    #[serde(rename = "helpfulImportantContext", with = "flag")]
+    pub helpful_important_context: bool,
+    #[serde(rename = "helpfulUnbiasedLanguage", with = "flag")]
+    pub helpful_unbiased_language: bool,
+    #[serde(rename = "notHelpfulOther", with = "flag")]
+    pub not_helpful_other: bool,
+    #[serde(rename = "notHelpfulIncorrect", with = "flag")]
+    pub not_helpful_incorrect: bool,
+    #[serde(rename = "notHelpfulSourcesMissingOrUnreliable", with = "flag")]
+    pub not_helpful_sources_missing_or_unreliable: bool,
+    #[serde(rename = "notHelpfulOpinionSpeculationOrBias", with = "flag")]
+    pub not_helpful_opinion_speculation_or_bias: bool,
+    #[serde(rename = "notHelpfulMissingKeyPoints", with = "flag")]
+    pub not_helpful_missing_key_points: bool,
+    #[serde(rename = "notHelpfulOutdated", with = "flag")]
+    pub not_helpful_outdated: bool,
+    #[serde(rename = "notHelpfulHardToUnderstand", with = "flag")]
+    pub not_helpful_hard_to_understand: bool,
+    #[serde(rename = "notHelpfulArgumentativeOrBiased", with = "flag")]
+    pub not_helpful_argumentative_or_biased: bool,
+    #[serde(rename = "notHelpfulOffTopic", with = "flag")]
+    pub not_helpful_off_topic: bool,
+    #[serde(rename = "notHelpfulSpamHarassmentOrAbuse", with = "flag")]
+    pub not_helpful_spam_harassment_or_abuse: bool,
+    #[serde(rename = "notHelpfulIrrelevantSources", with = "flag")]
+    pub not_helpful_irrelevant_sources: bool,
+    #[serde(rename = "notHelpfulOpinionSpeculation", with = "flag")]
+    pub not_helpful_opinion_speculation: bool,
+    #[serde(rename = "notHelpfulNoteNotNeeded", with = "flag")]
+    pub not_helpful_note_not_needed: bool,
+    #[serde(rename = "ratedOnTweetId", with = "optional_tweet_id")]
+    pub rated_on_tweet_id: Option<u64>,
+}
+
+impl RatingEntry<'_> {
+    /// The helpfulness level, falling back to the version 1 flags.
+    #[must_use]
+    pub const fn level(&self) -> Option<HelpfulnessLevel> {
+        match self.helpfulness_level {
+            HelpfulnessLevel::Empty => match (self.helpful, self.not_helpful) {
+                (true, false) => Some(HelpfulnessLevel::Helpful),
+                (false, true) => Some(HelpfulnessLevel::NotHelpful),
+                _ => None,
+            },
+            level => Some(level),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HelpfulnessLevel, RatingEntry};
+    use crate::model::fixtures::RATINGS;
+    use std::borrow::Cow;
+
+    #[test]
+    fn read_ratings() {
+        let mut reader = crate::tsv::Reader::new(RATINGS.as_bytes()).unwrap();
+
+        let first = reader.read::<RatingEntry<'_>>().unwrap().unwrap();
+
+        assert!(matches!(first.participant_id, Cow::Borrowed(_)));
+        assert_eq!(first.helpfulness_level, HelpfulnessLevel::Empty);
+        assert_eq!(first.level(), Some(HelpfulnessLevel::Helpful));
+        assert!(first.helpful_informative);
+        assert_eq!(first.rated_on_tweet_id, None);
+
+        let second = reader.read::<RatingEntry<'_>>().unwrap().unwrap();
+
+        assert_eq!(second.version, 2);
+        assert_eq!(second.level(), Some(HelpfulnessLevel::NotHelpful));
+        assert!(second.not_helpful_incorrect);
+        assert!(second.not_helpful_sources_missing_or_unreliable);
+        assert_eq!(second.rated_on_tweet_id, Some(1_400_247_230_330_667_009));
+
+        assert!(reader.read::<RatingEntry<'_>>().unwrap().is_none());
+    }
+
+    #[test]
+    fn read_ratings_with_extra_column() {
+        let ratings = RATINGS
+            .replace('\n', "\tx\n")
+            .replacen("\tx\n", "\tnewColumn\n", 1);
+        let mut reader = crate::tsv::Reader::new(ratings.as_bytes()).unwrap();
+
+        let first = reader.read::<RatingEntry<'_>>().unwrap().unwrap();
+
+        assert_eq!(first.level(), Some(HelpfulnessLevel::Helpful));
+        assert!(reader.read::<RatingEntry<'_>>().unwrap().is_some());
+        assert!(reader.read::<RatingEntry<'_>>().unwrap().is_none());
+    }
+}
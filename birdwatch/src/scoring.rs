@@ -0,0 +1,777 @@
+//! A reimplementation of the matrix factorization core scorer.
+//!
+//! Each rating is predicted as `μ + i_u + i_n + f_u · f_n`, where `μ` is a global intercept, `i_u`
+//! and `i_n` are rater and note intercepts, and `f_u` and `f_n` are one-dimensional rater and note
+//! factors. Parameters are fit by full-batch Adam on the mean squared error, with L2
+//! regularization (stronger for intercepts), and note statuses are assigned from the fitted note
+//! intercepts and factors (with a separate not-helpful threshold for notes classified as not
+//! misleading).
+//!
+//! Ratings are collected into a [`Matrix`] one row at a time, so the ratings files never need to
+//! be held in memory. Raters and notes with too few ratings are removed repeatedly until every
+//! remaining rater and note meets the minimum counts, as in the reference implementation.
+//!
+//! This follows the structure and default parameters of the reference implementation, but omits
+//! its later stages (for example rater helpfulness filtering and tag-based outcomes), so results
+//! are only expected to agree for most notes.
+use crate::model::{
+    Classification, NoteEntry, NoteStatusHistoryEntry, Status,
+    rating::{HelpfulnessLevel, RatingEntry},
+};
+use birdsite::model::graphql::birdwatch::note::model::Model;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Config {
+    pub l2_lambda: f64,
+    pub l2_intercept_multiplier: f64,
+    pub learning_rate: f64,
+    pub max_epochs: usize,
+    /// Training stops when the loss improves by less than this.
+    pub convergence: f64,
+    pub initial_factor_scale: f64,
+    pub seed: u64,
+    pub min_ratings_per_rater: usize,
+    pub min_ratings_per_note: usize,
+    pub helpful_intercept_threshold: f64,
+    pub max_helpful_factor: f64,
+    pub not_helpful_intercept_threshold: f64,
+    pub not_helpful_factor_multiplier: f64,
+    /// Notes classified as not misleading are also rated not helpful below this intercept.
+    pub not_misleading_not_helpful_intercept_threshold: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            l2_lambda: 0.03,
+            l2_intercept_multiplier: 5.0,
+            learning_rate: 0.2,
+            max_epochs: 2000,
+            convergence: 1e-7,
+            initial_factor_scale: 0.1,
+            seed: 0,
+            min_ratings_per_rater: 10,
+            min_ratings_per_note: 5,
+            helpful_intercept_threshold: 0.40,
+            max_helpful_factor: 0.5,
+            not_helpful_intercept_threshold: -0.05,
+            not_helpful_factor_multiplier: 0.8,
+            not_misleading_not_helpful_intercept_threshold: -0.15,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NoteScore {
+    pub intercept: f64,
+    pub factor: f64,
+    pub rating_count: usize,
+    pub status: Status,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RaterScore {
+    pub intercept: f64,
+    pub factor: f64,
+    pub rating_count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scores {
+    pub global_intercept: f64,
+    pub notes: BTreeMap<u64, NoteScore>,
+    pub raters: HashMap<String, RaterScore>,
+    pub epochs: usize,
+    pub loss: f64,
+}
+
+/// Agreement between computed statuses and the published status history.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Comparison {
+    pub agreed: usize,
+    /// Note ID, computed status, and published status.
+    pub disagreed: Vec<(u64, Status, Status)>,
+    /// Published statuses for notes that were not scored.
+    pub unscored: usize,
+}
+
+impl Scores {
+    /// Compare with published statuses.
+    ///
+    /// Only statuses decided by the core model (or with no recorded model) are compared.
+    pub fn compare<'a, I: IntoIterator<Item = &'a NoteStatusHistoryEntry<'a>>>(
+        &self,
+        history: I,
+    ) -> Comparison {
+        let mut comparison = Comparison::default();
+
+        for entry in history {
+            if entry
+                .current_decided_by
+                .is_none_or(|model| model == Model::CoreModel)
+            {
+                match self.notes.get(&entry.note_id) {
+                    Some(score) if score.status == entry.current_status => comparison.agreed += 1,
+                    Some(score) => comparison.disagreed.push((
+                        entry.note_id,
+                        score.status,
+                        entry.current_status,
+                    )),
+                    None => comparison.unscored += 1,
+                }
+            }
+        }
+
+        comparison
+    }
+}
+
+pub struct Scorer {
+    config: Config,
+}
+
+impl Default for Scorer {
+    fn default() -> Self {
+        Self::new(Config::default())
+    }
+}
+
+impl Scorer {
+    #[must_use]
+    pub const fn new(config: Config) -> Self {
+        Self { config }
+    }
+
+    /// Score the notes in the matrix.
+    #[must_use]
+    pub fn score(&self, matrix: &Matrix) -> Scores {
+        let data = Data::new(matrix, &self.config);
+        let mut model = Parameters::new(data.raters.len(), data.notes.len(), &self.config);
+        let (epochs, loss) = model.fit(&data, &self.config);
+
+        let notes = data
+            .notes
+            .iter()
+            .enumerate()
+            .map(|(index, note)| {
+                let intercept = model.note_intercepts[index];
+                let factor = model.note_factors[index];
+
+                (
+                    note.note_id,
+                    NoteScore {
+                        intercept,
+                        factor,
+                        rating_count: data.note_counts[index],
+                        status: self.status(intercept, factor, note.classification),
+                    },
+                )
+            })
+            .collect();
+
+        let raters = data
+            .raters
+            .iter()
+            .enumerate()
+            .map(|(index, rater_id)| {
+                (
+                    (*rater_id).to_string(),
+                    RaterScore {
+                        intercept: model.rater_intercepts[index],
+                        factor: model.rater_factors[index],
+                        rating_count: data.rater_counts[index],
+                    },
+                )
+            })
+            .collect();
+
+        Scores {
+            global_intercept: model.global_intercept,
+            notes,
+            raters,
+            epochs,
+            loss,
+        }
+    }
+
+    fn status(&self, intercept: f64, factor: f64, classification: Classification) -> Status {
+        if intercept >= self.config.helpful_intercept_threshold
+            && factor.abs() < self.config.max_helpful_factor
+        {
+            Status::Helpful
+        } else if intercept
+            <= self
+                .config
+                .not_helpful_factor_multiplier
+                .mul_add(-factor.abs(), self.config.not_helpful_intercept_threshold)
+            || (classification == Classification::NotMisleading
+                && intercept < self.config.not_misleading_not_helpful_intercept_threshold)
+        {
+            Status::NotHelpful
+        } else {
+            Status::NeedsMoreRatings
+        }
+    }
+}
+
+const fn rating_value(level: HelpfulnessLevel) -> Option<f64> {
+    match level {
+        HelpfulnessLevel::Helpful => Some(1.0),
+        HelpfulnessLevel::SomewhatHelpful => Some(0.5),
+        HelpfulnessLevel::NotHelpful => Some(0.0),
+        HelpfulnessLevel::Empty => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Note {
+    note_id: u64,
+    classification: Classification,
+}
+
+/// Ratings collected for scoring.
+///
+/// Only the rater, note, and value of each rating are kept. Ratings of notes that were not
+/// provided, ratings without a helpfulness value, and (for a historical replay) ratings and notes
+/// created at or after the cutoff are skipped.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Matrix {
+    as_of: Option<DateTime<Utc>>,
+    notes: Vec<Note>,
+    note_indices: HashMap<u64, usize>,
+    rater_indices: HashMap<String, usize>,
+    /// Rater index, note index, and value.
+    ratings: Vec<(usize, usize, f64)>,
+}
+
+impl Matrix {
+    /// A matrix for the given notes, using all ratings.
+    pub fn new<'a, 'b: 'a, I: IntoIterator<Item = &'a NoteEntry<'b>>>(notes: I) -> Self {
+        Self::with_cutoff(notes, None)
+    }
+
+    /// A matrix using only notes and ratings created before the given time, to replay historical
+    /// decisions.
+    pub fn as_of<'a, 'b: 'a, I: IntoIterator<Item = &'a NoteEntry<'b>>>(
+        notes: I,
+        as_of: DateTime<Utc>,
+    ) -> Self {
+        Self::with_cutoff(notes, Some(as_of))
+    }
+
+    fn with_cutoff<'a, 'b: 'a, I: IntoIterator<Item = &'a NoteEntry<'b>>>(
+        notes: I,
+        as_of: Option<DateTime<Utc>>,
+    ) -> Self {
+        let mut matrix = Self {
+            as_of,
+            ..Self::default()
+        };
+
+        for note in notes {
+            if matrix.is_before_cutoff(note.created_at)
+                && !matrix.note_indices.contains_key(&note.note_id)
+            {
+                matrix.note_indices.insert(note.note_id, matrix.notes.len());
+                matrix.notes.push(Note {
+                    note_id: note.note_id,
+                    classification: note.classification,
+                });
+            }
+        }
+
+        matrix
+    }
+
+    fn is_before_cutoff(&self, timestamp: DateTime<Utc>) -> bool {
+        self.as_of.is_none_or(|as_of| timestamp < as_of)
+    }
+
+    pub fn add_rating(&mut self, rating: &RatingEntry<'_>) {
+        if !self.is_before_cutoff(rating.created_at) {
+            return;
+        }
+
+        if let Some((note_index, value)) = self
+            .note_indices
+            .get(&rating.note_id)
+            .zip(rating.level().and_then(rating_value))
+        {
+            // The key is only allocated for raters we haven't seen yet.
+            let rater_index =
+                if let Some(rater_index) = self.rater_indices.get(rating.participant_id.as_ref()) {
+                    *rater_index
+                } else {
+                    let rater_index = self.rater_indices.len();
+
+                    self.rater_indices
+                        .insert(rating.participant_id.to_string(), rater_index);
+
+                    rater_index
+                };
+
+            self.ratings.push((rater_index, *note_index, value));
+        }
+    }
+
+    /// The number of ratings collected.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.ratings.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.ratings.is_empty()
+    }
+}
+
+/// Ratings indexed by rater and note, after filtering.
+struct Data<'m> {
+    raters: Vec<&'m str>,
+    notes: Vec<Note>,
+    rater_counts: Vec<usize>,
+    note_counts: Vec<usize>,
+    /// Rater index, note index, and value.
+    ratings: Vec<(usize, usize, f64)>,
+}
+
+impl<'m> Data<'m> {
+    fn new(matrix: &'m Matrix, config: &Config) -> Self {
+        let mut rater_ids = vec![""; matrix.rater_indices.len()];
+
+        for (rater_id, index) in &matrix.rater_indices {
+            rater_ids[*index] = rater_id;
+        }
+
+        let mut rater_included = vec![true; rater_ids.len()];
+        let mut note_included = vec![true; matrix.notes.len()];
+
+        // Removing raters can leave notes with too few ratings (and the reverse), so we repeat the
+        // filters until nothing changes.
+        loop {
+            let mut rater_counts = vec![0; rater_ids.len()];
+            let mut note_counts = vec![0; matrix.notes.len()];
+
+            for (rater_index, note_index, _) in &matrix.ratings {
+                if rater_included[*rater_index] && note_included[*note_index] {
+                    rater_counts[*rater_index] += 1;
+                    note_counts[*note_index] += 1;
+                }
+            }
+
+            let mut changed = false;
+
+            for (included, count) in rater_included.iter_mut().zip(rater_counts) {
+                if *included && count < config.min_ratings_per_rater {
+                    *included = false;
+                    changed = true;
+                }
+            }
+
+            for (included, count) in note_included.iter_mut().zip(note_counts) {
+                if *included && count < config.min_ratings_per_note {
+                    *included = false;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        let mut raters = (0..rater_ids.len())
+            .filter(|index| rater_included[*index])
+            .collect::<Vec<_>>();
+        let mut notes = (0..matrix.notes.len())
+            .filter(|index| note_included[*index])
+            .collect::<Vec<_>>();
+
+        // Sorting makes initialization (and therefore results) independent of input order.
+        raters.sort_unstable_by_key(|index| rater_ids[*index]);
+        notes.sort_unstable_by_key(|index| matrix.notes[*index].note_id);
+
+        let mut rater_indices = vec![None; rater_ids.len()];
+        let mut note_indices = vec![None; matrix.notes.len()];
+
+        for (new_index, old_index) in raters.iter().enumerate() {
+            rater_indices[*old_index] = Some(new_index);
+        }
+
+        for (new_index, old_index) in notes.iter().enumerate() {
+            note_indices[*old_index] = Some(new_index);
+        }
+
+        let ratings = matrix
+            .ratings
+            .iter()
+            .filter_map(|(rater_index, note_index, value)| {
+                rater_indices[*rater_index]
+                    .zip(note_indices[*note_index])
+                    .map(|(rater_index, note_index)| (rater_index, note_index, *value))
+            })
+            .collect::<Vec<_>>();
+
+        let mut rater_counts = vec![0; raters.len()];
+        let mut note_counts = vec![0; notes.len()];
+
+        for (rater_index, note_index, _) in &ratings {
+            rater_counts[*rater_index] += 1;
+            note_counts[*note_index] += 1;
+        }
+
+        Self {
+            raters: raters.into_iter().map(|index| rater_ids[index]).collect(),
+            notes: notes.into_iter().map(|index| matrix.notes[index]).collect(),
+            rater_counts,
+            note_counts,
+            ratings,
+        }
+    }
+}
+
+struct Parameters {
+    global_intercept: f64,
+    rater_intercepts: Vec<f64>,
+    note_intercepts: Vec<f64>,
+    rater_factors: Vec<f64>,
+    note_factors: Vec<f64>,
+}
+
+impl Parameters {
+    fn new(rater_count: usize, note_count: usize, config: &Config) -> Self {
+        let mut state = config.seed;
+        let mut next = || config.initial_factor_scale * 2.0f64.mul_add(uniform(&mut state), -1.0);
+
+        Self {
+            global_intercept: 0.0,
+            rater_intercepts: vec![0.0; rater_count],
+            note_intercepts: vec![0.0; note_count],
+            rater_factors: (0..rater_count).map(|_| next()).collect(),
+            note_factors: (0..note_count).map(|_| next()).collect(),
+        }
+    }
+
+    const fn len(&self) -> usize {
+        1 + 2 * (self.rater_intercepts.len() + self.note_intercepts.len())
+    }
+
+    /// The parameters in a fixed order (used for the optimizer state).
+    fn values_mut(&mut self) -> impl Iterator<Item = &mut f64> {
+        std::iter::once(&mut self.global_intercept)
+            .chain(self.rater_intercepts.iter_mut())
+            .chain(self.note_intercepts.iter_mut())
+            .chain(self.rater_factors.iter_mut())
+            .chain(self.note_factors.iter_mut())
+    }
+
+    /// Returns the number of epochs and the final loss.
+    fn fit(&mut self, data: &Data<'_>, config: &Config) -> (usize, f64) {
+        const BETA_1: f64 = 0.9;
+        const BETA_2: f64 = 0.999;
+        const EPSILON: f64 = 1e-8;
+
+        let mut first_moments = vec![0.0; self.len()];
+        let mut second_moments = vec![0.0; self.len()];
+        let mut previous_loss = f64::INFINITY;
+        let mut epochs = 0;
+
+        if data.ratings.is_empty() {
+            return (epochs, 0.0);
+        }
+
+        while epochs < config.max_epochs {
+            let (loss, gradients) = self.loss_and_gradients(data, config);
+
+            if (previous_loss - loss).abs() < config.convergence {
+                previous_loss = loss;
+                break;
+            }
+
+            previous_loss = loss;
+            epochs += 1;
+
+            let step = i32::try_from(epochs).unwrap_or(i32::MAX);
+            let first_correction = 1.0 - BETA_1.powi(step);
+            let second_correction = 1.0 - BETA_2.powi(step);
+
+            for (((value, gradient), first_moment), second_moment) in self
+                .values_mut()
+                .zip(&gradients)
+                .zip(&mut first_moments)
+                .zip(&mut second_moments)
+            {
+                *first_moment = BETA_1.mul_add(*first_moment, (1.0 - BETA_1) * gradient);
+                *second_moment =
+                    BETA_2.mul_add(*second_moment, (1.0 - BETA_2) * gradient * gradient);
+
+                let first = *first_moment / first_correction;
+                let second = *second_moment / second_correction;
+
+                *value -= config.learning_rate * first / (second.sqrt() + EPSILON);
+            }
+        }
+
+        (epochs, previous_loss)
+    }
+
+    // Counts are converted to floats for averaging, and will never be large enough to lose
+    // precision.
+    #[allow(clippy::cast_precision_loss)]
+    fn loss_and_gradients(&self, data: &Data<'_>, config: &Config) -> (f64, Vec<f64>) {
+        let rater_count = self.rater_intercepts.len();
+        let note_count = self.note_intercepts.len();
+        let n = data.ratings.len() as f64;
+        let factor_lambda = config.l2_lambda;
+        let intercept_lambda = config.l2_lambda * config.l2_intercept_multiplier;
+
+        let mut global_gradient = 0.0;
+        let mut rater_intercept_gradients = vec![0.0; rater_count];
+        let mut note_intercept_gradients = vec![0.0; note_count];
+        let mut rater_factor_gradients = vec![0.0; rater_count];
+        let mut note_factor_gradients = vec![0.0; note_count];
+        let mut squared_error = 0.0;
+
+        for (rater_index, note_index, value) in &data.ratings {
+            let rater_factor = self.rater_factors[*rater_index];
+            let note_factor = self.note_factors[*note_index];
+            let prediction = rater_factor.mul_add(
+                note_factor,
+                self.global_intercept
+                    + self.rater_intercepts[*rater_index]
+                    + self.note_intercepts[*note_index],
+            );
+            let error = prediction - value;
+            let scaled = 2.0 * error / n;
+
+            squared_error += error * error;
+            global_gradient += scaled;
+            rater_intercept_gradients[*rater_index] += scaled;
+            note_intercept_gradients[*note_index] += scaled;
+            rater_factor_gradients[*rater_index] += scaled * note_factor;
+            note_factor_gradients[*note_index] += scaled * rater_factor;
+        }
+
+        let mean_square = |values: &[f64]| {
+            if values.is_empty() {
+                0.0
+            } else {
+                values.iter().map(|value| value * value).sum::<f64>() / values.len() as f64
+            }
+        };
+
+        let factor_penalty = mean_square(&self.rater_factors) + mean_square(&self.note_factors);
+        let intercept_penalty = self.global_intercept.mul_add(
+            self.global_intercept,
+            mean_square(&self.rater_intercepts) + mean_square(&self.note_intercepts),
+        );
+        let loss = intercept_lambda.mul_add(
+            intercept_penalty,
+            factor_lambda.mul_add(factor_penalty, squared_error / n),
+        );
+
+        global_gradient += 2.0 * intercept_lambda * self.global_intercept;
+
+        let regularize = |gradients: &mut [f64], values: &[f64], lambda: f64| {
+            let scale = 2.0 * lambda / values.len() as f64;
+
+            for (gradient, value) in gradients.iter_mut().zip(values) {
+                *gradient += scale * value;
+            }
+        };
+
+        regularize(
+            &mut rater_intercept_gradients,
+            &self.rater_intercepts,
+            intercept_lambda,
+        );
+        regularize(
+            &mut note_intercept_gradients,
+            &self.note_intercepts,
+            intercept_lambda,
+        );
+        regularize(
+            &mut rater_factor_gradients,
+            &self.rater_factors,
+            factor_lambda,
+        );
+        regularize(
+            &mut note_factor_gradients,
+            &self.note_factors,
+            factor_lambda,
+        );
+
+        let gradients = std::iter::once(global_gradient)
+            .chain(rater_intercept_gradients)
+            .chain(note_intercept_gradients)
+            .chain(rater_factor_gradients)
+            .chain(note_factor_gradients)
+            .collect();
+
+        (loss, gradients)
+    }
+}
+
+/// A uniform value in `[0, 1)` from a `SplitMix64` generator (so results are reproducible).
+#[allow(clippy::cast_precision_loss)]
+fn uniform(state: &mut u64) -> f64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, Matrix, Scorer};
+    use crate::model::{Classification, NoteEntry, Status, rating::RatingEntry};
+    use chrono::DateTime;
+    use std::fmt::Write;
+
+    /// Thirteen notes, created a millisecond apart.
+    fn notes() -> Vec<NoteEntry<'static>> {
+        (0..13)
+            .map(|note_id| NoteEntry {
+                created_at: DateTime::from_timestamp_millis(1_700_000_000_000 + note_id).unwrap(),
+                classification: Classification::Misleading,
+                ..crate::model::fixtures::note_entry(note_id.try_into().unwrap(), "A")
+            })
+            .collect()
+    }
+
+    /// Twenty raters in two groups rate twelve notes: four that everyone finds helpful, four that
+    /// only one group finds helpful, and four that nobody finds helpful.
+    fn ratings() -> String {
+        let header = "noteId\traterParticipantId\tcreatedAtMillis\tversion\tagree\tdisagree\thelpful\tnotHelpful\thelpfulnessLevel";
+        let mut tsv = format!("{header}\n");
+
+        for rater in 0..20 {
+            for note in 0..12 {
+                let helpful = match note / 4 {
+                    0 => true,
+                    1 => rater % 2 == 0,
+                    _ => false,
+                };
+                let level = if helpful { "HELPFUL" } else { "NOT_HELPFUL" };
+
+                writeln!(
+                    tsv,
+                    "{note}\tR{rater:02}\t{}\t2\t0\t0\t0\t0\t{level}",
+                    1_700_000_000_000_u64 + note
+                )
+                .unwrap();
+            }
+        }
+
+        tsv
+    }
+
+    /// Stream the ratings into the matrix.
+    fn add_ratings(matrix: &mut Matrix, tsv: &str) {
+        let mut reader = crate::tsv::Reader::new(tsv.as_bytes()).unwrap();
+
+        while let Some(rating) = reader.read::<Partial<'_>>().unwrap() {
+            matrix.add_rating(&RatingEntry {
+                created_at: DateTime::from_timestamp_millis(rating.created_at).unwrap(),
+                helpfulness_level: rating.helpfulness_level,
+                ..crate::model::fixtures::rating_entry(rating.note_id, rating.participant_id)
+            });
+        }
+    }
+
+    /// Only the columns the scorer needs.
+    #[derive(serde::Deserialize)]
+    struct Partial<'a> {
+        #[serde(rename = "noteId")]
+        note_id: u64,
+        #[serde(rename = "raterParticipantId")]
+        participant_id: &'a str,
+        #[serde(rename = "createdAtMillis")]
+        created_at: i64,
+        #[serde(rename = "helpfulnessLevel")]
+        helpfulness_level: crate::model::rating::HelpfulnessLevel,
+    }
+
+    #[test]
+    fn score_polarized_ratings() {
+        let mut matrix = Matrix::new(&notes());
+        add_ratings(&mut matrix, &ratings());
+
+        let scores = Scorer::default().score(&matrix);
+
+        for note_id in 0..12 {
+            let score = scores.notes[&note_id];
+            let expected = match note_id / 4 {
+                0 => Status::Helpful,
+                1 => Status::NeedsMoreRatings,
+                _ => Status::NotHelpful,
+            };
+
+            assert_eq!(score.status, expected, "note {note_id}: {score:?}");
+            assert_eq!(score.rating_count, 20);
+        }
+
+        // The polarized notes should be explained by the factors rather than the intercepts.
+        assert!(scores.notes[&4].factor.abs() > scores.notes[&0].factor.abs());
+    }
+
+    #[test]
+    fn score_as_of_excludes_later_notes_and_ratings() {
+        let as_of = DateTime::from_timestamp_millis(1_700_000_000_006).unwrap();
+        let mut matrix = Matrix::as_of(&notes(), as_of);
+        add_ratings(&mut matrix, &ratings());
+
+        // Each rater has only rated six notes by this time.
+        let scores = Scorer::new(Config {
+            min_ratings_per_rater: 5,
+            ..Config::default()
+        })
+        .score(&matrix);
+
+        assert_eq!(matrix.len(), 6 * 20);
+        assert_eq!(scores.notes.len(), 6);
+        assert!(scores.notes.keys().all(|note_id| *note_id < 6));
+    }
+
+    #[test]
+    fn filters_repeat_until_stable() {
+        let mut tsv = ratings();
+
+        // Note 12 is rated by five raters with no other ratings, and by a rater who has rated
+        // nine other notes. Once the five raters are removed, note 12 has too few ratings, and
+        // once note 12 is removed, the remaining rater has too few ratings.
+        for rater in 0..5 {
+            writeln!(tsv, "12\tX{rater}\t1700000000012\t2\t0\t0\t0\t0\tHELPFUL").unwrap();
+        }
+
+        for note in (0..9).chain(std::iter::once(12)) {
+            writeln!(
+                tsv,
+                "{note}\tY\t{}\t2\t0\t0\t0\t0\tHELPFUL",
+                1_700_000_000_000_u64 + note
+            )
+            .unwrap();
+        }
+
+        // Ratings of notes that were not provided are skipped.
+        writeln!(tsv, "99\tR00\t1700000000099\t2\t0\t0\t0\t0\tHELPFUL").unwrap();
+
+        let mut matrix = Matrix::new(&notes());
+        add_ratings(&mut matrix, &tsv);
+
+        let scores = Scorer::default().score(&matrix);
+
+        assert_eq!(matrix.len(), 20 * 12 + 5 + 10);
+        assert!(!scores.notes.contains_key(&12));
+        assert!(!scores.raters.contains_key("Y"));
+        assert_eq!(scores.raters.len(), 20);
+        assert_eq!(scores.notes[&0].rating_count, 20);
+    }
+}
@@ -0,0 +1,53 @@
+//! Streaming reader for the public data TSV files.
+//!
+//! Rows are decoded in place from a reused buffer, so borrowed fields (for example participant
+//! IDs) do not allocate.
+//!
+//! Columns that a row type does not model (for example ones added to the published files after the
+//! type was written) are ignored, so new columns don't break existing readers.
+use std::io::Read;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("CSV error")]
+    Csv(#[from] csv::Error),
+}
+
+pub struct Reader<R> {
+    inner: csv::Reader<R>,
+    headers: csv::ByteRecord,
+    record: csv::ByteRecord,
+}
+
+impl<R: Read> Reader<R> {
+    /// Create a reader and read the header row.
+    pub fn new(reader: R) -> Result<Self, Error> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(b'\t')
+            .has_headers(true)
+            .from_reader(reader);
+
+        let headers = reader.byte_headers()?.clone();
+
+        Ok(Self {
+            inner: reader,
+            headers,
+            record: csv::ByteRecord::new(),
+        })
+    }
+
+    /// Read the next row, which may borrow from the reader's buffer.
+    pub fn read<'a, T: serde::de::Deserialize<'a>>(&'a mut self) -> Result<Option<T>, Error> {
+        if self.inner.read_byte_record(&mut self.record)? {
+            Ok(Some(self.record.deserialize(Some(&self.headers))?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// The current line number (one-based, including the header row).
+    #[must_use]
+    pub fn line_number(&self) -> u64 {
+        self.record.position().map_or(1, csv::Position::line)
+    }
+}
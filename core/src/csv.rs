@@ -0,0 +1,395 @@
+//! CSV export of tweets and users with selectable column sets.
+//!
+//! Columns are always written in the same order (the order of the column sets below), no matter
+//! how the sets are specified, so that files written with the same sets can be concatenated.
+//! Missing values are written as empty fields.
+use crate::model::{tweet::Tweet, user::User};
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt::Display;
+use std::io::Write;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("CSV error")]
+    Csv(#[from] ::csv::Error),
+    #[error("Invalid column set: {0}")]
+    InvalidColumnSet(String),
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ColumnSet {
+    Ids,
+    Timestamps,
+    Text,
+    Metrics,
+    ReplyTarget,
+    Verification,
+    Source,
+    Lang,
+}
+
+impl ColumnSet {
+    pub const ALL: [Self; 8] = [
+        Self::Ids,
+        Self::Timestamps,
+        Self::Text,
+        Self::Metrics,
+        Self::ReplyTarget,
+        Self::Verification,
+        Self::Source,
+        Self::Lang,
+    ];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Ids => "ids",
+            Self::Timestamps => "timestamps",
+            Self::Text => "text",
+            Self::Metrics => "metrics",
+            Self::ReplyTarget => "reply-target",
+            Self::Verification => "verification",
+            Self::Source => "source",
+            Self::Lang => "lang",
+        }
+    }
+}
+
+impl FromStr for ColumnSet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|column_set| column_set.as_str() == s)
+            .ok_or_else(|| Error::InvalidColumnSet(s.to_string()))
+    }
+}
+
+impl Display for ColumnSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A type that can be written as a CSV row.
+pub trait Record {
+    /// The column names for a set (empty if the set does not apply to this type).
+    fn headers(column_set: ColumnSet) -> &'static [&'static str];
+    /// Add one field for each column name in the set.
+    fn append_fields(&self, column_set: ColumnSet, fields: &mut Vec<String>);
+}
+
+/// A record type without a lifetime, so that a writer can be tied to it while each row borrows
+/// from data that only lives for a single iteration.
+pub trait RecordType {
+    type Row<'a>: Record;
+}
+
+/// Rows of [`TweetRow`].
+pub enum Tweets {}
+
+impl RecordType for Tweets {
+    type Row<'a> = TweetRow<'a>;
+}
+
+/// Rows of [`User`].
+pub enum Users {}
+
+impl RecordType for Users {
+    type Row<'a> = User<'a>;
+}
+
+/// A tweet together with its author and the author of the tweet it replies to (if known).
+#[derive(Clone, Copy, Debug)]
+pub struct TweetRow<'a> {
+    pub tweet: &'a Tweet<'a>,
+    pub author: Option<&'a User<'a>>,
+    pub in_reply_to_user: Option<&'a User<'a>>,
+}
+
+impl Record for TweetRow<'_> {
+    fn headers(column_set: ColumnSet) -> &'static [&'static str] {
+        match column_set {
+            ColumnSet::Ids => &["tweet_id", "author_id", "conversation_id"],
+            ColumnSet::Timestamps => &["created_at"],
+            ColumnSet::Text => &["text"],
+            ColumnSet::Metrics => &[
+                "retweet_count",
+                "reply_count",
+                "like_count",
+                "quote_count",
+                "author_followers_count",
+                "author_tweet_count",
+            ],
+            ColumnSet::ReplyTarget => &[
+                "in_reply_to_tweet_id",
+                "in_reply_to_user_id",
+                "in_reply_to_screen_name",
+            ],
+            ColumnSet::Verification => &["author_verified", "in_reply_to_verified"],
+            ColumnSet::Source => &["source"],
+            ColumnSet::Lang => &["lang"],
+        }
+    }
+
+    fn append_fields(&self, column_set: ColumnSet, fields: &mut Vec<String>) {
+        let tweet = self.tweet;
+
+        match column_set {
+            ColumnSet::Ids => {
+                fields.push(tweet.id.to_string());
+                fields.push(tweet.author_id.to_string());
+                fields.push(optional(tweet.conversation_id));
+            }
+            ColumnSet::Timestamps => fields.push(timestamp(Some(tweet.created_at))),
+            ColumnSet::Text => fields.push(tweet.text.to_string()),
+            ColumnSet::Metrics => {
+                fields.push(optional(tweet.retweet_count));
+                fields.push(optional(tweet.reply_count));
+                fields.push(optional(tweet.like_count));
+                fields.push(optional(tweet.quote_count));
+                fields.push(optional(self.author.and_then(|user| user.followers_count)));
+                fields.push(optional(self.author.and_then(|user| user.statuses_count)));
+            }
+            ColumnSet::ReplyTarget => {
+                fields.push(optional(tweet.in_reply_to_tweet_id));
+                fields.push(optional(tweet.in_reply_to_user_id));
+                fields.push(optional(
+                    self.in_reply_to_user
+                        .and_then(|user| user.screen_name.as_ref()),
+                ));
+            }
+            ColumnSet::Verification => {
+                fields.push(optional(self.author.and_then(|user| user.verified)));
+                fields.push(optional(
+                    self.in_reply_to_user.and_then(|user| user.verified),
+                ));
+            }
+            ColumnSet::Source => fields.push(optional(tweet.source.as_ref())),
+            ColumnSet::Lang => fields.push(optional(tweet.lang)),
+        }
+    }
+}
+
+impl Record for User<'_> {
+    fn headers(column_set: ColumnSet) -> &'static [&'static str] {
+        match column_set {
+            ColumnSet::Ids => &["user_id"],
+            ColumnSet::Timestamps => &["created_at"],
+            ColumnSet::Text => &["screen_name", "name", "description", "location"],
+            ColumnSet::Metrics => &[
+                "followers_count",
+                "friends_count",
+                "statuses_count",
+                "listed_count",
+            ],
+            ColumnSet::Verification => &["verified", "blue_verified", "protected"],
+            ColumnSet::ReplyTarget | ColumnSet::Source | ColumnSet::Lang => &[],
+        }
+    }
+
+    fn append_fields(&self, column_set: ColumnSet, fields: &mut Vec<String>) {
+        match column_set {
+            ColumnSet::Ids => fields.push(self.id.to_string()),
+            ColumnSet::Timestamps => fields.push(timestamp(self.created_at)),
+            ColumnSet::Text => {
+                fields.push(optional(self.screen_name.as_ref()));
+                fields.push(optional(self.name.as_ref()));
+                fields.push(optional(self.description.as_ref()));
+                fields.push(optional(self.location.as_ref()));
+            }
+            ColumnSet::Metrics => {
+                fields.push(optional(self.followers_count));
+                fields.push(optional(self.friends_count));
+                fields.push(optional(self.statuses_count));
+                fields.push(optional(self.listed_count));
+            }
+            ColumnSet::Verification => {
+                fields.push(optional(self.verified));
+                fields.push(optional(self.blue_verified));
+                fields.push(optional(self.protected));
+            }
+            ColumnSet::ReplyTarget | ColumnSet::Source | ColumnSet::Lang => {}
+        }
+    }
+}
+
+fn optional<T: Display>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+fn timestamp(value: Option<DateTime<Utc>>) -> String {
+    value
+        .map(|value| value.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+/// Writes records of a single type with a header row.
+pub struct Writer<W: Write, T: RecordType> {
+    inner: ::csv::Writer<W>,
+    column_sets: Vec<ColumnSet>,
+    fields: Vec<String>,
+    _record_type: PhantomData<T>,
+}
+
+impl<W: Write, T: RecordType> Writer<W, T> {
+    /// The header row is written immediately.
+    pub fn new(writer: W, column_sets: &[ColumnSet]) -> Result<Self, Error> {
+        let mut column_sets = column_sets.to_vec();
+        column_sets.sort_unstable();
+        column_sets.dedup();
+
+        let mut inner = ::csv::Writer::from_writer(writer);
+
+        inner.write_record(
+            column_sets
+                .iter()
+                .flat_map(|column_set| T::Row::<'static>::headers(*column_set)),
+        )?;
+
+        Ok(Self {
+            inner,
+            column_sets,
+            fields: vec![],
+            _record_type: PhantomData,
+        })
+    }
+
+    pub fn write(&mut self, record: &T::Row<'_>) -> Result<(), Error> {
+        self.fields.clear();
+
+        for column_set in &self.column_sets {
+            record.append_fields(*column_set, &mut self.fields);
+        }
+
+        Ok(self.inner.write_record(&self.fields)?)
+    }
+
+    pub fn flush(&mut self) -> Result<(), Error> {
+        Ok(self.inner.flush().map_err(::csv::Error::from)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ColumnSet, TweetRow, Tweets, Users, Writer};
+    use crate::model::{
+        tweet::Tweet,
+        user::User,
+        wxj::{data::TweetSnapshot, flat},
+    };
+
+    const SNAPSHOT: &str = concat!(
+        r#"{"data":{"id":"2","author_id":"10","conversation_id":"1","#,
+        r#""created_at":"2025-08-01T00:00:00.000Z","lang":"en","possibly_sensitive":false,"#,
+        r#""public_metrics":{"retweet_count":0,"reply_count":0,"like_count":3,"quote_count":0},"#,
+        r#""reply_settings":"everyone","text":"Hello, \"world\"","source":"Twitter Web App","#,
+        r#""in_reply_to_user_id":"11","referenced_tweets":[{"type":"replied_to","id":"1"}]},"#,
+        r#""includes":{"users":[{"id":"10","username":"alice","name":"Alice","#,
+        r#""created_at":"2010-01-01T00:00:00.000Z","description":"","#,
+        r#""profile_image_url":"https://example.com/a.jpg","verified":false,"protected":false,"#,
+        r#""public_metrics":{"followers_count":5,"following_count":1,"tweet_count":2,"#,
+        r#""listed_count":0}}]}}"#
+    );
+
+    #[test]
+    fn write_tweet_rows() {
+        let snapshot = serde_json::from_str::<TweetSnapshot<'_>>(SNAPSHOT).unwrap();
+        let tweet = Tweet::from_data(&snapshot.data).unwrap();
+        let author = snapshot.lookup_user(tweet.author_id).map(User::from);
+
+        let mut output = vec![];
+        let mut writer = Writer::<_, Tweets>::new(
+            &mut output,
+            &[
+                ColumnSet::Text,
+                ColumnSet::Ids,
+                ColumnSet::ReplyTarget,
+                ColumnSet::Ids,
+            ],
+        )
+        .unwrap();
+
+        writer
+            .write(&TweetRow {
+                tweet: &tweet,
+                author: author.as_ref(),
+                in_reply_to_user: None,
+            })
+            .unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            concat!(
+                "tweet_id,author_id,conversation_id,text,in_reply_to_tweet_id,in_reply_to_user_id,",
+                "in_reply_to_screen_name\n",
+                "2,10,1,\"Hello, \"\"world\"\"\",1,11,\n"
+            )
+        );
+        assert_eq!(
+            "reply-target".parse::<ColumnSet>().unwrap(),
+            ColumnSet::ReplyTarget
+        );
+    }
+
+    #[test]
+    fn write_flat_rows() {
+        // A synthetic snapshot (not a capture), with a photo and an animated GIF.
+        let snapshot = serde_json::from_str::<flat::TweetSnapshot<'_>>(include_str!(
+            "../../examples/wxj/flat-tweet-synthetic.json"
+        ))
+        .unwrap();
+        let tweet = Tweet::from_flat(&snapshot);
+        let author = User::from(&snapshot.user);
+
+        let mut output = vec![];
+        let mut writer = Writer::<_, Tweets>::new(&mut output, &ColumnSet::ALL).unwrap();
+
+        writer
+            .write(&TweetRow {
+                tweet: &tweet,
+                author: Some(&author),
+                in_reply_to_user: None,
+            })
+            .unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            concat!(
+                "tweet_id,author_id,conversation_id,created_at,text,retweet_count,reply_count,",
+                "like_count,quote_count,author_followers_count,author_tweet_count,",
+                "in_reply_to_tweet_id,in_reply_to_user_id,in_reply_to_screen_name,author_verified,",
+                "in_reply_to_verified,source,lang\n",
+                "1580540000000000002,10,,2022-10-13T12:00:00Z,@bob Look at this https://t.co/AbCdEfGhIj,",
+                "1,0,3,0,5,2,1580530000000000001,11,,false,,Twitter Web App,en\n"
+            )
+        );
+
+        let mut output = vec![];
+        let mut writer = Writer::<_, Users>::new(
+            &mut output,
+            &[ColumnSet::Ids, ColumnSet::Text, ColumnSet::Metrics],
+        )
+        .unwrap();
+
+        writer.write(&author).unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            concat!(
+                "user_id,screen_name,name,description,location,followers_count,friends_count,",
+                "statuses_count,listed_count\n",
+                "10,alice,Alice,Photos,Berlin,5,1,2,0\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Likes, retweets, and quotes from any source.
+//!
+//! GraphQL engagements come from `Favoriters` and `Retweeters` edges (see
+//! [`graph`](crate::model::graphql::graph)) and from `Likes` timelines, while `wxj` engagements
+//! come from retweet and quote references in tweet metadata.
+use crate::model::{
+    graphql::{
+        graph::{Edge, Relation},
+        timeline::instruction::Timeline,
+    },
+    wxj::metadata::tweet::TweetMetadata,
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
+)]
+pub enum Kind {
+    Like,
+    Retweet,
+    Quote,
+}
+
+/// A user engaging with a tweet.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
+)]
+pub struct Engagement {
+    pub kind: Kind,
+    /// The engaging user.
+    pub user_id: u64,
+    /// The tweet engaged with.
+    pub tweet_id: u64,
+    /// The author of the tweet engaged with (if known).
+    pub author_id: Option<u64>,
+    /// The retweet or quote tweet (likes do not have one).
+    pub engagement_tweet_id: Option<u64>,
+    /// When the engagement happened (only known for retweets and quotes).
+    pub created_at: Option<DateTime<Utc>>,
+    /// When the engagement was observed (only known for GraphQL sources).
+    pub observed_at: Option<DateTime<Utc>>,
+}
+
+impl Engagement {
+    /// Convert a `Favoriters` or `Retweeters` edge (follow edges are ignored).
+    #[must_use]
+    pub fn from_edge(edge: &Edge<'_>) -> Option<Self> {
+        let (kind, tweet_id) = match edge.relation {
+            Relation::Liked { tweet_id } => Some((Kind::Like, tweet_id)),
+            Relation::Retweeted { tweet_id } => Some((Kind::Retweet, tweet_id)),
+            Relation::Follows => None,
+        }?;
+
+        Some(Self {
+            kind,
+            user_id: edge.source,
+            tweet_id,
+            author_id: Some(edge.target),
+            engagement_tweet_id: None,
+            created_at: None,
+            observed_at: Some(edge.observed_at),
+        })
+    }
+
+    /// Extract likes from a user's `Likes` timeline.
+    ///
+    /// The `tweet_ids` function returns the ID and author ID for a tweet result (if available).
+    pub fn from_likes_timeline<'a, 'b, T, U, F: Fn(&T) -> Option<(u64, u64)> + 'b>(
+        user_id: u64,
+        timeline: &'b Timeline<'a, T, U>,
+        observed_at: DateTime<Utc>,
+        tweet_ids: F,
+    ) -> impl Iterator<Item = Self> + 'b
+    where
+        'a: 'b,
+    {
+        timeline.tweets().filter_map(move |tweet| {
+            tweet_ids(tweet).map(|(tweet_id, author_id)| Self {
+                kind: Kind::Like,
+                user_id,
+                tweet_id,
+                author_id: Some(author_id),
+                engagement_tweet_id: None,
+                created_at: None,
+                observed_at: Some(observed_at),
+            })
+        })
+    }
+
+    /// Extract retweets and quotes from tweet metadata.
+    ///
+    /// Authors of engaged tweets are resolved from the other tweets in the slice (for example the
+    /// referenced tweets returned by `TweetMetadata::from_tweet_snapshot`). A retweet carries the
+    /// quoted ID of the tweet it retweets, so quotes are only extracted for tweets that are not
+    /// retweets.
+    #[must_use]
+    pub fn from_tweet_metadata(tweets: &[TweetMetadata]) -> Vec<Self> {
+        let authors = tweets
+            .iter()
+            .map(|tweet| (tweet.id, tweet.user.id))
+            .collect::<HashMap<_, _>>();
+
+        tweets
+            .iter()
+            .flat_map(|tweet| {
+                [
+                    tweet.retweeted_id.map(|id| (Kind::Retweet, id)),
+                    tweet
+                        .quoted_id
+                        .filter(|_| tweet.retweeted_id.is_none())
+                        .map(|id| (Kind::Quote, id)),
+                ]
+                .into_iter()
+                .flatten()
+                .map(|(kind, tweet_id)| Self {
+                    kind,
+                    user_id: tweet.user.id,
+                    tweet_id,
+                    author_id: authors.get(&tweet_id).copied(),
+                    engagement_tweet_id: Some(tweet.id),
+                    created_at: Some(tweet.created_at),
+                    observed_at: None,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Engagement, Kind};
+    use crate::model::{
+        graphql::graph::{Edge, Relation},
+        wxj::metadata::tweet::{TweetMetadata, UserMetadata},
+    };
+    use chrono::DateTime;
+
+    #[test]
+    fn engagements_from_metadata_and_edges() {
+        let t0 = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let t1 = DateTime::from_timestamp(1_700_000_100, 0).unwrap();
+
+        let tweets = [
+            TweetMetadata::new(2, UserMetadata::new(20, None), t1, Some(1), None, None),
+            TweetMetadata::new(1, UserMetadata::new(10, None), t0, None, None, Some(3)),
+        ];
+
+        let engagements = Engagement::from_tweet_metadata(&tweets);
+
+        assert_eq!(engagements.len(), 2);
+        assert_eq!(engagements[0].kind, Kind::Retweet);
+        assert_eq!(engagements[0].user_id, 20);
+        assert_eq!(engagements[0].author_id, Some(10));
+        assert_eq!(engagements[0].created_at, Some(t1));
+        assert_eq!(engagements[1].kind, Kind::Quote);
+        assert_eq!(engagements[1].author_id, None);
+
+        let edge = Edge {
+            source: 30,
+            target: 10,
+            relation: Relation::Liked { tweet_id: 1 },
+            observed_at: t1,
+            page_cursor: None,
+        };
+
+        let engagement = Engagement::from_edge(&edge).unwrap();
+
+        assert_eq!(engagement.kind, Kind::Like);
+        assert_eq!(engagement.tweet_id, 1);
+        assert_eq!(engagement.author_id, Some(10));
+        assert!(
+            Engagement::from_edge(&Edge {
+                relation: Relation::Follows,
+                ..edge
+            })
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn retweet_of_quote_is_not_a_quote() {
+        let t0 = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let t1 = DateTime::from_timestamp(1_700_000_100, 0).unwrap();
+
+        let tweets = [
+            TweetMetadata::new(2, UserMetadata::new(20, None), t1, Some(1), None, Some(3)),
+            TweetMetadata::new(1, UserMetadata::new(10, None), t0, None, None, Some(3)),
+        ];
+
+        let engagements = Engagement::from_tweet_metadata(&tweets)
+            .into_iter()
+            .map(|engagement| (engagement.kind, engagement.user_id, engagement.tweet_id))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            engagements,
+            vec![(Kind::Retweet, 20, 1), (Kind::Quote, 10, 3)]
+        );
+    }
+}
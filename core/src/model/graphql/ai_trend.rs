@@ -0,0 +1,101 @@
+//! Generated trend summaries (returned for `AiTrendByRestId` requests).
+//!
+//! No captured responses confirm the full field set yet, so unknown fields are ignored rather than
+//! denied.
+use crate::model::graphql::{ResultWrapper, text::Text};
+use chrono::{DateTime, Utc, serde::ts_milliseconds_option};
+use serde_field_attributes::integer_str;
+use std::borrow::Cow;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "__typename")]
+pub enum AiTrendResult<'a, T> {
+    AiTrend {
+        #[serde(with = "integer_str")]
+        rest_id: u64,
+        #[serde(borrow)]
+        core: Core<'a>,
+        summary: Option<Text<'a>>,
+        disclaimer: Option<Cow<'a, str>>,
+        /// The referenced tweets.
+        post_results: Option<Vec<ResultWrapper<T>>>,
+        #[serde(with = "ts_milliseconds_option", default)]
+        created_at: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option", default)]
+        last_updated_at: Option<DateTime<Utc>>,
+    },
+    AiTrendUnavailable {},
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Core<'a> {
+    /// The headline.
+    #[serde(borrow)]
+    pub name: Cow<'a, str>,
+}
+
+impl<T> AiTrendResult<'_, T> {
+    /// The referenced tweets that are available.
+    pub fn posts(&self) -> impl Iterator<Item = &T> {
+        let post_results = match self {
+            Self::AiTrend { post_results, .. } => post_results.as_deref(),
+            Self::AiTrendUnavailable {} => None,
+        };
+
+        post_results
+            .into_iter()
+            .flatten()
+            .filter_map(|result| result.result.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[derive(Debug, serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct AiTrendResponse<'a> {
+        #[serde(borrow)]
+        data: AiTrendData<'a>,
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct AiTrendData<'a> {
+        #[serde(borrow)]
+        ai_trend: crate::model::graphql::ResultWrapper<super::AiTrendResult<'a, serde_json::Value>>,
+    }
+
+    #[test]
+    fn deserialize_ai_trend() {
+        // A synthetic response (not a capture), with a single available tweet.
+        let json = include_str!("../../../../examples/graphql/ai-trend-synthetic.json");
+
+        let response = serde_json::from_str::<AiTrendResponse<'_>>(json).unwrap();
+        let result = response.data.ai_trend.result.unwrap();
+
+        assert_eq!(
+            result
+                .posts()
+                .map(|post| post["rest_id"].as_str())
+                .collect::<Vec<_>>(),
+            vec![Some("1925620000000000001")]
+        );
+
+        match result {
+            super::AiTrendResult::AiTrend {
+                rest_id,
+                core,
+                summary,
+                created_at,
+                last_updated_at,
+                ..
+            } => {
+                assert_eq!(rest_id, 1_925_623_158_000_001_024);
+                assert_eq!(core.name, "Storm hits the Gulf Coast");
+                assert_eq!(summary.unwrap().entities.len(), 1);
+                assert!(last_updated_at > created_at);
+            }
+            super::AiTrendResult::AiTrendUnavailable {} => panic!("Expected available trend"),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! Contributor enrollment states (shared with the public data files).
+
+/// The public data files use camel case (`earnedIn`), while GraphQL responses use Pascal case
+/// (`EarnedIn`), so both are accepted.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum EnrollmentState {
+    #[serde(rename = "newUser", alias = "NewUser")]
+    NewUser,
+    #[serde(rename = "earnedIn", alias = "EarnedIn")]
+    EarnedIn,
+    #[serde(rename = "atRisk", alias = "AtRisk")]
+    AtRisk,
+    #[serde(rename = "earnedOutNoAcknowledge", alias = "EarnedOutNoAcknowledge")]
+    EarnedOutNoAcknowledge,
+    #[serde(rename = "earnedOutAcknowledged", alias = "EarnedOutAcknowledged")]
+    EarnedOutAcknowledged,
+}
+
+impl EnrollmentState {
+    /// Whether the contributor can currently write notes.
+    #[must_use]
+    pub const fn can_write(self) -> bool {
+        matches!(self, Self::EarnedIn | Self::AtRisk)
+    }
+}
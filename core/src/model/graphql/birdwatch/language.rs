@@ -0,0 +1,207 @@
+//! Note counts and statuses by language.
+//!
+//! Observed note shares can be compared with the baseline in [`stats`], which is based on account
+//! languages rather than tweet languages, so the comparison is only a rough measure of coverage.
+use super::note::{Note, RatingStatus};
+use crate::model::lang::{Lang, Language, stats};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LanguageStats {
+    pub note_count: usize,
+    pub helpful_count: usize,
+    pub not_helpful_count: usize,
+    pub needs_more_ratings_count: usize,
+    /// Notes shown in the language of the viewing account.
+    pub in_account_language_count: usize,
+}
+
+impl LanguageStats {
+    /// The number of notes with a rating status.
+    #[must_use]
+    pub const fn status_count(&self) -> usize {
+        self.helpful_count + self.not_helpful_count + self.needs_more_ratings_count
+    }
+
+    // Counts will never be large enough to lose precision.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn helpful_rate(&self) -> Option<f64> {
+        let count = self.status_count();
+
+        (count > 0).then(|| self.helpful_count as f64 / count as f64)
+    }
+}
+
+/// The observed share of notes in a language compared with the baseline share of accounts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Coverage {
+    pub language: Language,
+    pub note_count: usize,
+    pub observed_share: f64,
+    pub expected_share: f64,
+}
+
+impl Coverage {
+    /// Greater than one if the language has more notes than the baseline would suggest.
+    ///
+    /// Returns `None` if the baseline share is zero.
+    #[must_use]
+    pub fn ratio(&self) -> Option<f64> {
+        (self.expected_share != 0.0).then(|| self.observed_share / self.expected_share)
+    }
+}
+
+/// Notes grouped by language (`None` for notes without a language).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LanguageAggregation {
+    languages: BTreeMap<Option<Lang>, LanguageStats>,
+    total: usize,
+}
+
+impl LanguageAggregation {
+    /// Empty notes (with no metadata) are ignored.
+    pub fn add(&mut self, note: &Note<'_>) {
+        if let Some(metadata) = note.metadata() {
+            let language_stats = self.languages.entry(metadata.language).or_default();
+
+            language_stats.note_count += 1;
+
+            match metadata.rating_status {
+                Some(RatingStatus::CurrentlyRatedHelpful) => language_stats.helpful_count += 1,
+                Some(RatingStatus::CurrentlyRatedNotHelpful) => {
+                    language_stats.not_helpful_count += 1;
+                }
+                Some(RatingStatus::NeedsMoreRatings) => {
+                    language_stats.needs_more_ratings_count += 1;
+                }
+                None => {}
+            }
+
+            if note
+                .data()
+                .and_then(|data| data.is_in_account_language)
+                .unwrap_or(false)
+            {
+                language_stats.in_account_language_count += 1;
+            }
+
+            self.total += 1;
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, lang: Option<Lang>) -> Option<&LanguageStats> {
+        self.languages.get(&lang)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Option<Lang>, &LanguageStats)> {
+        self.languages
+            .iter()
+            .map(|(lang, language_stats)| (*lang, language_stats))
+    }
+
+    /// The total number of notes.
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Compare note shares with the baseline for every recognized language, ordered by descending
+    /// note count.
+    ///
+    /// Shares are computed relative to all notes, including those without a recognized language.
+    // Counts will never be large enough to lose precision.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn coverage(&self) -> Vec<Coverage> {
+        let mut coverage = self
+            .iter()
+            .filter_map(|(lang, language_stats)| match lang {
+                Some(Lang::Language(language)) => Some(Coverage {
+                    language,
+                    note_count: language_stats.note_count,
+                    observed_share: language_stats.note_count as f64 / self.total as f64,
+                    expected_share: f64::from(stats::percentage(&language)),
+                }),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        coverage.sort_by_key(|coverage| std::cmp::Reverse(coverage.note_count));
+
+        coverage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Coverage, LanguageAggregation};
+    use crate::model::{graphql::birdwatch::note::Note, lang::Lang};
+
+    fn note(language: &str, rating_status: &str) -> String {
+        format!(
+            concat!(
+                r#"{{"rest_id":"1","data_v1":{{"classification":"NotMisleading","#,
+                r#""summary":{{"text":"Context","entities":[]}}}},"created_at":1756184544431,"#,
+                r#""rating_status":"{}","language":"{}","is_in_account_language":true}}"#
+            ),
+            rating_status, language
+        )
+    }
+
+    #[test]
+    fn aggregate_languages() {
+        let json = [
+            note("en", "CurrentlyRatedHelpful"),
+            note("en", "NeedsMoreRatings"),
+            note("en", "NeedsMoreRatings"),
+            note("ja", "CurrentlyRatedHelpful"),
+        ];
+
+        let mut aggregation = LanguageAggregation::default();
+
+        for json in &json {
+            aggregation.add(&serde_json::from_str::<Note<'_>>(json).unwrap());
+        }
+
+        let english = "en".parse::<Lang>().unwrap();
+        let english_stats = aggregation.get(Some(english)).unwrap();
+
+        assert_eq!(aggregation.total(), 4);
+        assert_eq!(english_stats.note_count, 3);
+        assert_eq!(english_stats.in_account_language_count, 3);
+        assert_eq!(english_stats.helpful_rate(), Some(1.0 / 3.0));
+
+        let coverage = aggregation.coverage();
+
+        assert_eq!(coverage.len(), 2);
+        assert_eq!(Lang::Language(coverage[0].language), english);
+        assert!((coverage[0].observed_share - 0.75).abs() < f64::EPSILON);
+        // Japanese is over-represented relative to its share of accounts.
+        assert!(coverage[1].ratio().unwrap() > coverage[0].ratio().unwrap());
+    }
+
+    #[test]
+    fn ratio_without_baseline() {
+        let Lang::Language(language) = "en".parse::<Lang>().unwrap() else {
+            panic!("Expected a recognized language");
+        };
+        let coverage = Coverage {
+            language,
+            note_count: 1,
+            observed_share: 1.0,
+            expected_share: 0.0,
+        };
+
+        assert_eq!(coverage.ratio(), None);
+        assert_eq!(
+            Coverage {
+                expected_share: 0.5,
+                ..coverage
+            }
+            .ratio(),
+            Some(2.0)
+        );
+    }
+}
@@ -54,6 +54,14 @@ pub struct Bundle {
     pub bat_signals: Option<FileSet>,
 }
 
+/// The response to a `BirdwatchFetchPublicData` request.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PublicData {
+    #[serde(rename = "birdwatch_latest_public_data_file_bundle")]
+    pub bundle: Bundle,
+}
+
 impl Bundle {
     pub fn downloads<P: AsRef<Path>>(&self, base: P) -> Result<Vec<(url::Url, PathBuf)>, Error> {
         let mut result = Vec::with_capacity(13);
@@ -100,17 +108,12 @@ impl Bundle {
 
 #[cfg(test)]
 mod tests {
-    #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
-    struct Manifest {
-        birdwatch_latest_public_data_file_bundle: super::Bundle,
-    }
-
     #[test]
     fn deserialize_birdwatch_examples() {
         let line =
             include_str!("../../../../../examples/graphql/birdwatch-manifest-2025-08-28.json");
 
-        let result = serde_json::from_str::<Manifest>(line);
+        let result = serde_json::from_str::<super::PublicData>(line);
 
         assert!(result.is_ok());
     }
@@ -1,3 +1,7 @@
+pub mod enrollment;
+pub mod language;
 pub mod manifest;
 pub mod note;
 pub mod profile;
+pub mod timeline;
+pub mod translation;
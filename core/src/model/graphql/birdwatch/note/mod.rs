@@ -19,6 +19,32 @@ pub enum Note<'a> {
     },
 }
 
+impl<'a> Note<'a> {
+    #[must_use]
+    pub const fn id(&self) -> u64 {
+        match self {
+            Self::Available { metadata, .. } | Self::Unavailable { metadata } => metadata.id,
+            Self::Empty { id } => *id,
+        }
+    }
+
+    #[must_use]
+    pub const fn metadata(&self) -> Option<&NoteMetadata<'a>> {
+        match self {
+            Self::Available { metadata, .. } | Self::Unavailable { metadata } => Some(metadata),
+            Self::Empty { .. } => None,
+        }
+    }
+
+    #[must_use]
+    pub fn data(&self) -> Option<&NoteData<'a>> {
+        match self {
+            Self::Available { data, .. } => Some(data),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct NoteMetadata<'a> {
     pub id: u64,
@@ -1,3 +1,4 @@
+use super::enrollment::EnrollmentState;
 use chrono::{DateTime, Utc, serde::ts_milliseconds};
 use std::borrow::Cow;
 
@@ -12,6 +13,20 @@ pub struct Profile<'a> {
     pub is_top_writer: Option<bool>,
 }
 
+/// The profile of the logged-in contributor (`BirdwatchFetchAuthenticatedUserProfile`).
+///
+/// This includes enrollment details that are not shown on public profiles.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuthenticatedUserProfile<'a> {
+    /// The fields shared with public profiles.
+    #[serde(flatten, borrow)]
+    pub profile: Profile<'a>,
+    pub enrollment_state: Option<EnrollmentState>,
+    pub successful_rating_needed_to_earn_in: Option<usize>,
+    pub is_alias_self_selected: Option<bool>,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct RatingsCount {
@@ -43,3 +58,34 @@ pub struct NotesCount {
     #[serde(with = "ts_milliseconds")]
     pub last_updated_at: DateTime<Utc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AuthenticatedUserProfile, EnrollmentState};
+
+    #[test]
+    fn deserialize_authenticated_user_profile() {
+        let profile = serde_json::from_str::<AuthenticatedUserProfile<'_>>(concat!(
+            r#"{"alias":"brave-blue-heron","notes_count":{"currently_rated_helpful":3,"#,
+            r#""last_updated_at":1756184544431},"enrollment_state":"EarnedIn","#,
+            r#""successful_rating_needed_to_earn_in":5}"#
+        ))
+        .unwrap();
+
+        assert_eq!(profile.enrollment_state, Some(EnrollmentState::EarnedIn));
+        assert_eq!(profile.profile.alias.as_deref(), Some("brave-blue-heron"));
+        assert_eq!(
+            profile
+                .profile
+                .notes_count
+                .and_then(|notes_count| notes_count.currently_rated_helpful),
+            Some(3)
+        );
+        assert!(
+            serde_json::from_str::<AuthenticatedUserProfile<'_>>(
+                r#"{"alias":"brave-blue-heron","rating_impact":{"total":12}}"#
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Lists of notes (returned for `BirdwatchFetchGlobalTimeline` and
+//! `BirdwatchFetchContributorNotesSlice` requests).
+use super::note::Note;
+use std::borrow::Cow;
+
+/// The `birdwatch_home_page` object (`BirdwatchFetchGlobalTimeline`).
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GlobalTimeline<'a> {
+    #[serde(borrow)]
+    pub body: GlobalTimelineBody<'a>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GlobalTimelineBody<'a> {
+    /// The tabs of the home page (for example "New" and "Needs your help").
+    #[serde(borrow, default)]
+    pub timelines: Vec<Section<'a>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Section<'a> {
+    #[serde(borrow)]
+    pub title: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    pub timeline: NotesSlice<'a>,
+}
+
+impl<'a> GlobalTimeline<'a> {
+    /// The notes from every section, in order.
+    pub fn notes(&self) -> impl Iterator<Item = &Note<'a>> {
+        self.body
+            .timelines
+            .iter()
+            .flat_map(|section| &section.timeline.items)
+    }
+}
+
+/// A page of notes (`BirdwatchFetchContributorNotesSlice`, and the sections of the global
+/// timeline).
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NotesSlice<'a> {
+    #[serde(borrow, default)]
+    pub items: Vec<Note<'a>>,
+    #[serde(borrow)]
+    pub slice_info: Option<SliceInfo<'a>>,
+}
+
+impl NotesSlice<'_> {
+    #[must_use]
+    pub fn next_cursor(&self) -> Option<&str> {
+        self.slice_info
+            .as_ref()
+            .and_then(|slice_info| slice_info.next_cursor.as_deref())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SliceInfo<'a> {
+    #[serde(borrow)]
+    pub next_cursor: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    pub previous_cursor: Option<Cow<'a, str>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GlobalTimeline, NotesSlice};
+
+    const NOTE: &str = r#"{"rest_id":"1959814924454297777","data_v1":{"classification":"NotMisleading","summary":{"text":"Context","entities":[]}},"rating_status":"NeedsMoreRatings","created_at":1756184544431}"#;
+
+    #[test]
+    fn deserialize_global_timeline() {
+        let json = format!(
+            r#"{{"body":{{"timelines":[{{"title":"New","timeline":{{"items":[{NOTE}],"slice_info":{{"next_cursor":"abc"}}}}}},{{"title":"Needs your help","timeline":{{"items":[]}}}}]}}}}"#
+        );
+
+        let timeline = serde_json::from_str::<GlobalTimeline<'_>>(&json).unwrap();
+
+        assert_eq!(timeline.body.timelines.len(), 2);
+        assert_eq!(
+            timeline.body.timelines[0].timeline.next_cursor(),
+            Some("abc")
+        );
+        assert_eq!(
+            timeline.notes().map(super::Note::id).collect::<Vec<_>>(),
+            vec![1_959_814_924_454_297_777]
+        );
+    }
+
+    #[test]
+    fn deserialize_contributor_notes_slice() {
+        let json = format!(r#"{{"items":[{NOTE},{NOTE}],"slice_info":{{"previous_cursor":"x"}}}}"#);
+
+        let slice = serde_json::from_str::<NotesSlice<'_>>(&json).unwrap();
+
+        assert_eq!(slice.items.len(), 2);
+        assert_eq!(slice.next_cursor(), None);
+    }
+}
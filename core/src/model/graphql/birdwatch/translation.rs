@@ -0,0 +1,50 @@
+//! Machine translations of notes (returned for `BirdwatchFetchNoteTranslation` requests).
+use crate::model::lang::Lang;
+use std::borrow::Cow;
+
+/// The `birdwatch_note_translation` object.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NoteTranslation<'a> {
+    #[serde(borrow)]
+    pub translation: Cow<'a, str>,
+    /// Missing when the source language was not detected.
+    pub source_language: Option<Lang>,
+    pub destination_language: Lang,
+}
+
+impl NoteTranslation<'_> {
+    /// Whether the note was already in the destination language.
+    #[must_use]
+    pub fn is_identity(&self) -> bool {
+        self.source_language == Some(self.destination_language)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NoteTranslation;
+    use crate::model::lang::Lang;
+
+    #[test]
+    fn deserialize_note_translation() {
+        let translation = serde_json::from_str::<NoteTranslation<'_>>(
+            r#"{"translation":"The image is from 2019.","source_language":"es","destination_language":"en"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            translation.source_language,
+            Some("es".parse::<Lang>().unwrap())
+        );
+        assert_eq!(
+            translation.destination_language,
+            "en".parse::<Lang>().unwrap()
+        );
+        assert!(!translation.is_identity());
+        assert!(matches!(
+            translation.translation,
+            std::borrow::Cow::Borrowed(_)
+        ));
+    }
+}
@@ -0,0 +1,77 @@
+//! Live video broadcasts (returned for `BroadcastQuery` requests).
+use crate::model::graphql::user::IdentifiedUserResults;
+use chrono::{DateTime, Utc};
+use serde_field_attributes::{optional_integer_str, optional_timestamp_millis_str};
+use std::borrow::Cow;
+
+/// A broadcast's identity, state, timing, and audience counts.
+///
+/// No captured responses confirm the full field set, so unknown fields are not denied. Display
+/// fields (for example `image_url` and the broadcaster's `username` and `profile_image_url`) and
+/// stream settings (for example `is_high_latency` and `content_type`) are ignored.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Broadcast<'a, U> {
+    /// Broadcast IDs are alphanumeric (for example `1yNxaNvaMbBxj`).
+    #[serde(borrow)]
+    pub id: Cow<'a, str>,
+    pub media_key: Option<Cow<'a, str>>,
+    pub title: Option<Cow<'a, str>>,
+    pub state: State,
+    pub language: Option<Cow<'a, str>>,
+    #[serde(with = "optional_timestamp_millis_str", default)]
+    pub start_ms: Option<DateTime<Utc>>,
+    #[serde(with = "optional_timestamp_millis_str", default)]
+    pub end_ms: Option<DateTime<Utc>>,
+    #[serde(with = "optional_timestamp_millis_str", default)]
+    pub scheduled_start_ms: Option<DateTime<Utc>>,
+    #[serde(with = "optional_timestamp_millis_str", default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    pub available_for_replay: Option<bool>,
+    #[serde(with = "optional_integer_str", default)]
+    pub total_watched: Option<usize>,
+    #[serde(with = "optional_integer_str", default)]
+    pub total_watching: Option<usize>,
+    #[serde(with = "optional_integer_str", default)]
+    pub tweet_id: Option<u64>,
+    pub user_results: Option<IdentifiedUserResults<U>>,
+}
+
+impl<U> Broadcast<'_, U> {
+    #[must_use]
+    pub fn is_replay_available(&self) -> bool {
+        self.available_for_replay.unwrap_or(false)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum State {
+    NotStarted,
+    Running,
+    Ended,
+    TimedOut,
+    Canceled,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::graphql::user::User;
+
+    #[test]
+    fn deserialize_broadcast() {
+        let json = concat!(
+            r#"{"id":"1yNxaNvaMbBxj","media_key":"28_1700000000000000000","title":"Launch","#,
+            r#""state":"ENDED","language":"en","start_ms":"1700000400000","#,
+            r#""end_ms":"1700004000000","available_for_replay":true,"total_watched":"1234","#,
+            r#""tweet_id":"1700000000000000001","user_results":{"rest_id":"12","result":{"#,
+            r#""__typename":"User","rest_id":"12","legacy":{"screen_name":"jack","name":"jack"}}}}"#
+        );
+
+        let broadcast = serde_json::from_str::<super::Broadcast<'_, User<'_>>>(json).unwrap();
+
+        assert_eq!(broadcast.state, super::State::Ended);
+        assert!(broadcast.is_replay_available());
+        assert_eq!(broadcast.total_watched, Some(1234));
+        assert!(broadcast.end_ms > broadcast.start_ms);
+    }
+}
@@ -0,0 +1,413 @@
+//! Changes between snapshots of the same community.
+use super::{Community, JoinPolicy, Rule, Theme};
+use chrono::{DateTime, Utc};
+use std::borrow::Cow;
+
+#[derive(thiserror::Error, Debug, Eq, PartialEq)]
+pub enum Error {
+    #[error("Community IDs do not match (expected {expected}, actual {actual})")]
+    IdMismatch { expected: u64, actual: u64 },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Change<'a> {
+    Name {
+        old: Cow<'a, str>,
+        new: Cow<'a, str>,
+    },
+    Description {
+        old: Option<Cow<'a, str>>,
+        new: Option<Cow<'a, str>>,
+    },
+    RuleAdded(Rule<'a>),
+    RuleRemoved(Rule<'a>),
+    RuleChanged {
+        old: Rule<'a>,
+        new: Rule<'a>,
+    },
+    /// The order of rules changed (without any other rule changes).
+    RulesReordered,
+    /// The effective theme (the custom theme if there is one, or the default).
+    Theme {
+        old: Option<Theme>,
+        new: Option<Theme>,
+    },
+    JoinPolicy {
+        old: JoinPolicy,
+        new: JoinPolicy,
+    },
+    MemberCount {
+        old: usize,
+        new: usize,
+    },
+    ModeratorCount {
+        old: usize,
+        new: usize,
+    },
+    /// A change of admin, identified by user ID.
+    Admin {
+        old: Option<u64>,
+        new: Option<u64>,
+    },
+    Nsfw {
+        old: Option<bool>,
+        new: Option<bool>,
+    },
+}
+
+impl Change<'_> {
+    #[must_use]
+    pub const fn is_rule_change(&self) -> bool {
+        matches!(
+            self,
+            Self::RuleAdded(_)
+                | Self::RuleRemoved(_)
+                | Self::RuleChanged { .. }
+                | Self::RulesReordered
+        )
+    }
+}
+
+/// Compare two snapshots of the same community.
+///
+/// The `user_id` function is used to identify admins.
+pub fn diff<'a, U, F: Fn(&U) -> u64>(
+    old: &Community<'a, U>,
+    new: &Community<'a, U>,
+    user_id: F,
+) -> Result<Vec<Change<'a>>, Error> {
+    if old.id != new.id {
+        return Err(Error::IdMismatch {
+            expected: old.id,
+            actual: new.id,
+        });
+    }
+
+    let mut changes = vec![];
+
+    if old.name != new.name {
+        changes.push(Change::Name {
+            old: old.name.clone(),
+            new: new.name.clone(),
+        });
+    }
+
+    if old.description != new.description {
+        changes.push(Change::Description {
+            old: old.description.clone(),
+            new: new.description.clone(),
+        });
+    }
+
+    diff_rules(&old.rules, &new.rules, &mut changes);
+
+    let old_theme = old.custom_theme.or(old.default_theme);
+    let new_theme = new.custom_theme.or(new.default_theme);
+
+    if old_theme != new_theme {
+        changes.push(Change::Theme {
+            old: old_theme,
+            new: new_theme,
+        });
+    }
+
+    if old.join_policy != new.join_policy {
+        changes.push(Change::JoinPolicy {
+            old: old.join_policy,
+            new: new.join_policy,
+        });
+    }
+
+    if old.member_count != new.member_count {
+        changes.push(Change::MemberCount {
+            old: old.member_count,
+            new: new.member_count,
+        });
+    }
+
+    if old.moderator_count != new.moderator_count {
+        changes.push(Change::ModeratorCount {
+            old: old.moderator_count,
+            new: new.moderator_count,
+        });
+    }
+
+    let old_admin = old.admin_results.result.as_ref().map(&user_id);
+    let new_admin = new.admin_results.result.as_ref().map(&user_id);
+
+    if old_admin != new_admin {
+        changes.push(Change::Admin {
+            old: old_admin,
+            new: new_admin,
+        });
+    }
+
+    if old.is_nsfw != new.is_nsfw {
+        changes.push(Change::Nsfw {
+            old: old.is_nsfw,
+            new: new.is_nsfw,
+        });
+    }
+
+    Ok(changes)
+}
+
+/// Rules are matched by ID.
+fn diff_rules<'a>(old: &[Rule<'a>], new: &[Rule<'a>], changes: &mut Vec<Change<'a>>) {
+    let initial_len = changes.len();
+
+    for old_rule in old {
+        match new
+            .iter()
+            .find(|new_rule| new_rule.rest_id == old_rule.rest_id)
+        {
+            Some(new_rule) if new_rule != old_rule => changes.push(Change::RuleChanged {
+                old: old_rule.clone(),
+                new: new_rule.clone(),
+            }),
+            Some(_) => {}
+            None => changes.push(Change::RuleRemoved(old_rule.clone())),
+        }
+    }
+
+    for new_rule in new {
+        if !old
+            .iter()
+            .any(|old_rule| old_rule.rest_id == new_rule.rest_id)
+        {
+            changes.push(Change::RuleAdded(new_rule.clone()));
+        }
+    }
+
+    if changes.len() == initial_len
+        && !old
+            .iter()
+            .map(|rule| rule.rest_id)
+            .eq(new.iter().map(|rule| rule.rest_id))
+    {
+        changes.push(Change::RulesReordered);
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoryEntry<'a> {
+    /// When the earlier of the two compared snapshots was observed.
+    pub previous_observed_at: DateTime<Utc>,
+    pub observed_at: DateTime<Utc>,
+    pub changes: Vec<Change<'a>>,
+}
+
+/// The changes to a single community across a sequence of snapshots.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct History<'a> {
+    pub id: u64,
+    pub first_observed_at: DateTime<Utc>,
+    pub last_observed_at: DateTime<Utc>,
+    pub snapshot_count: usize,
+    /// Only includes comparisons where something changed.
+    pub entries: Vec<HistoryEntry<'a>>,
+}
+
+impl<'a> History<'a> {
+    /// Build a history from timestamped snapshots (which may be provided in any order).
+    ///
+    /// Returns `None` if there are no snapshots.
+    pub fn build<
+        'c,
+        U: 'c,
+        F: Fn(&U) -> u64,
+        I: IntoIterator<Item = (DateTime<Utc>, &'c Community<'a, U>)>,
+    >(
+        snapshots: I,
+        user_id: F,
+    ) -> Result<Option<Self>, Error>
+    where
+        'a: 'c,
+    {
+        let mut snapshots = snapshots.into_iter().collect::<Vec<_>>();
+        snapshots.sort_by_key(|(observed_at, _)| *observed_at);
+
+        let Some(((first_observed_at, first), (last_observed_at, _))) =
+            snapshots.first().zip(snapshots.last())
+        else {
+            return Ok(None);
+        };
+
+        let mut entries = vec![];
+
+        for pair in snapshots.windows(2) {
+            let (previous_observed_at, previous) = pair[0];
+            let (observed_at, current) = pair[1];
+            let changes = diff(previous, current, &user_id)?;
+
+            if !changes.is_empty() {
+                entries.push(HistoryEntry {
+                    previous_observed_at,
+                    observed_at,
+                    changes,
+                });
+            }
+        }
+
+        Ok(Some(Self {
+            id: first.id,
+            first_observed_at: *first_observed_at,
+            last_observed_at: *last_observed_at,
+            snapshot_count: snapshots.len(),
+            entries,
+        }))
+    }
+
+    /// Entries where the admin changed.
+    pub fn admin_changes(&self) -> impl Iterator<Item = &HistoryEntry<'a>> {
+        self.entries.iter().filter(|entry| {
+            entry
+                .changes
+                .iter()
+                .any(|change| matches!(change, Change::Admin { .. }))
+        })
+    }
+
+    /// Entries where the rules changed.
+    pub fn rule_changes(&self) -> impl Iterator<Item = &HistoryEntry<'a>> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.changes.iter().any(Change::is_rule_change))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Change, Community, Error, History};
+    use crate::model::graphql::{community::CommunityResult, user::User};
+    use chrono::DateTime;
+
+    const COMMUNITIES_2024_08_01: &str =
+        include_str!("../../../../../examples/graphql/communities-2024-08-01.ndjson");
+    const COMMUNITIES_2025_06_01: &str =
+        include_str!("../../../../../examples/graphql/communities-2025-06-01.ndjson");
+
+    fn communities(ndjson: &'static str) -> Vec<Community<'static, User<'static>>> {
+        ndjson
+            .lines()
+            .map(|line| {
+                match serde_json::from_str::<CommunityResult<'_, User<'_>>>(line).unwrap() {
+                    CommunityResult::Community { community } => *community,
+                    CommunityResult::CommunityUnavailable {} => {
+                        panic!("Expected available community")
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn community(ndjson: &'static str, id: u64) -> Community<'static, User<'static>> {
+        communities(ndjson)
+            .into_iter()
+            .find(|community| community.id == id)
+            .unwrap()
+    }
+
+    #[test]
+    fn diff_identical_is_empty() {
+        for community in communities(COMMUNITIES_2025_06_01) {
+            assert_eq!(
+                super::diff(&community, &community, |user| user.rest_id),
+                Ok(vec![])
+            );
+        }
+    }
+
+    #[test]
+    fn diff_dated_fixtures() {
+        let old = communities(COMMUNITIES_2024_08_01);
+        let new = communities(COMMUNITIES_2025_06_01);
+
+        // The two collections do not have any communities in common, so every comparison fails.
+        for old in &old {
+            for new in &new {
+                assert_eq!(
+                    super::diff(old, new, |user| user.rest_id),
+                    Err(Error::IdMismatch {
+                        expected: old.id,
+                        actual: new.id
+                    })
+                );
+            }
+        }
+
+        let error = super::diff(&old[0], &new[0], |user| user.rest_id).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Community IDs do not match (expected {}, actual {})",
+                old[0].id, new[0].id
+            )
+        );
+    }
+
+    #[test]
+    fn history_detects_changes() {
+        // The Apple community on 1 August 2024.
+        let first = community(COMMUNITIES_2024_08_01, 1_461_479_793_287_778_304);
+        // A rule from The Evidence Index on 1 June 2025.
+        let added_rule =
+            community(COMMUNITIES_2025_06_01, 1_771_628_627_542_581_489).rules[0].clone();
+
+        let mut second = first.clone();
+        second.name = "Renamed".into();
+        second.member_count += 10;
+        let removed_rule = second.rules.remove(6);
+        second.rules[3].name = "English only, please".into();
+        second.rules.push(added_rule.clone());
+        if let Some(admin) = second.admin_results.result.as_mut() {
+            admin.rest_id = 1;
+        }
+        let third = second.clone();
+
+        let t0 = DateTime::from_timestamp(1_722_470_400, 0).unwrap();
+        let t1 = DateTime::from_timestamp(1_735_689_600, 0).unwrap();
+        let t2 = DateTime::from_timestamp(1_748_736_000, 0).unwrap();
+
+        let history = History::build([(t2, &third), (t0, &first), (t1, &second)], |user| {
+            user.rest_id
+        })
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(history.snapshot_count, 3);
+        assert_eq!(history.first_observed_at, t0);
+        assert_eq!(history.last_observed_at, t2);
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(history.entries[0].observed_at, t1);
+        assert_eq!(history.admin_changes().count(), 1);
+        assert_eq!(history.rule_changes().count(), 1);
+
+        assert_eq!(removed_rule.name, "No giveaways");
+        assert_eq!(
+            history.entries[0]
+                .changes
+                .iter()
+                .filter(|change| change.is_rule_change())
+                .cloned()
+                .collect::<Vec<_>>(),
+            vec![
+                Change::RuleChanged {
+                    old: first.rules[3].clone(),
+                    new: second.rules[3].clone()
+                },
+                Change::RuleRemoved(removed_rule),
+                Change::RuleAdded(added_rule),
+            ]
+        );
+        assert!(history.entries[0].changes.contains(&Change::Name {
+            old: "Apple".into(),
+            new: "Renamed".into()
+        }));
+        assert!(history.entries[0].changes.contains(&Change::MemberCount {
+            old: 16142,
+            new: 16152
+        }));
+    }
+}
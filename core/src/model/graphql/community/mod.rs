@@ -0,0 +1,461 @@
+use crate::model::graphql::{ResultWrapper, timeline::instruction::TimelineWrapper};
+use chrono::{DateTime, Utc, serde::ts_milliseconds};
+use serde_field_attributes::{integer_str, optional_integer_str};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+pub mod history;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "__typename", deny_unknown_fields)]
+pub enum CommunityResult<'a, U> {
+    Community {
+        #[serde(flatten)]
+        community: Box<Community<'a, U>>,
+    },
+    CommunityUnavailable {},
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Community<'a, U> {
+    #[serde(rename = "id_str", with = "integer_str")]
+    pub id: u64,
+    pub name: Cow<'a, str>,
+    pub description: Option<Cow<'a, str>>,
+    #[serde(with = "ts_milliseconds")]
+    pub created_at: DateTime<Utc>,
+    pub default_theme: Option<Theme>,
+    pub custom_theme: Option<Theme>,
+    pub question: Option<Cow<'a, str>>,
+    pub search_tags: Option<Vec<Cow<'a, str>>>,
+    pub is_nsfw: Option<bool>,
+    pub actions: Actions,
+    pub primary_community_topic: Option<Topic<'a>>,
+    pub admin_results: ResultWrapper<U>,
+    pub creator_results: ResultWrapper<U>,
+    pub invites_result: InvitesResult,
+    pub join_policy: JoinPolicy,
+    pub invites_policy: InvitesPolicy,
+    pub is_pinned: bool,
+    pub members_facepile_results: Vec<ResultWrapper<U>>,
+    pub moderator_count: usize,
+    pub member_count: usize,
+    pub role: Role,
+    pub rules: Vec<Rule<'a>>,
+    pub custom_banner_media: Option<BannerMedia<'a>>,
+    pub default_banner_media: BannerMedia<'a>,
+    pub viewer_relationship: ViewerRelationship,
+    pub join_requests_result: JoinRequestsResult,
+    #[serde(rename = "id")]
+    _internal_id: Option<Cow<'a, str>>,
+    #[serde(rename = "rest_id", with = "optional_integer_str", default)]
+    _rest_id: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Theme {
+    Default,
+    Blue,
+    Green,
+    Magenta,
+    Orange,
+    Plum,
+    Purple,
+    Red,
+    Teal,
+    Yellow,
+}
+
+/// These are interface elements we don't care about.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Actions {}
+
+/// These are interface elements we don't care about.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct InvitesResult {}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Topic<'a> {
+    #[serde(with = "integer_str")]
+    pub topic_id: u64,
+    pub topic_name: Cow<'a, str>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum JoinPolicy {
+    Open,
+    RestrictedJoinRequestsDisabled,
+    RestrictedJoinRequestsRequireModeratorApproval,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum InvitesPolicy {
+    MemberInvitesAllowed,
+    ModeratorInvitesAllowed,
+}
+
+/// Ordered by privilege.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
+)]
+pub enum Role {
+    NonMember,
+    Member,
+    Moderator,
+    Admin,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule<'a> {
+    #[serde(with = "integer_str")]
+    pub rest_id: u64,
+    pub name: Cow<'a, str>,
+    pub description: Option<Cow<'a, str>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BannerMedia<'a> {
+    pub media_info: MediaInfo<'a>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MediaInfo<'a> {
+    pub color_info: ColorInfo,
+    pub original_img_url: Cow<'a, str>,
+    pub original_img_width: usize,
+    pub original_img_height: usize,
+    pub salient_rect: Option<crate::model::graphql::shapes::Rectangle>,
+}
+
+/// These are interface elements we don't care about.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ColorInfo {}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ViewerRelationship {
+    pub moderation_state: ModerationState,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "__typename")]
+pub enum ModerationState {
+    #[serde(rename = "CommunityUserDefaultModerationState")]
+    Default,
+    /// Any other moderation state (these are only visible to moderators).
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "__typename")]
+pub enum JoinRequestsResult {
+    #[serde(rename = "CommunityJoinRequestsUnavailable")]
+    Unavailable,
+    /// Join request details are only visible to moderators, and we do not currently model them.
+    #[serde(other)]
+    Other,
+}
+
+/// The community result for `membersSliceTimeline_Query` and `moderatorsSliceTimeline_Query`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "__typename")]
+pub enum CommunityMembersResult<'a, U> {
+    Community {
+        #[serde(borrow)]
+        members_slice: Option<Slice<'a, U>>,
+        moderators_slice: Option<Slice<'a, U>>,
+    },
+    CommunityUnavailable {},
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Slice<'a, U> {
+    pub items_results: Vec<ResultWrapper<U>>,
+    #[serde(borrow)]
+    pub slice_info: SliceInfo<'a>,
+}
+
+impl<U> Slice<'_, U> {
+    pub fn users(&self) -> impl Iterator<Item = &U> {
+        self.items_results
+            .iter()
+            .filter_map(|result| result.result.as_ref())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SliceInfo<'a> {
+    #[serde(borrow)]
+    pub next_cursor: Option<Cow<'a, str>>,
+    pub previous_cursor: Option<Cow<'a, str>>,
+}
+
+/// The community result for `CommunityTweetsTimeline`.
+///
+/// The ranked ("Top") and chronological ("Latest") timelines appear under different fields.
+// Results are read in place from the parsed response, so the variant size difference does not cost
+// us any copying.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "__typename")]
+pub enum CommunityTimelineResult<'a, T, U> {
+    Community {
+        #[serde(borrow)]
+        ranked_community_timeline: Option<TimelineWrapper<'a, T, U>>,
+        community_timeline: Option<TimelineWrapper<'a, T, U>>,
+    },
+    CommunityUnavailable {},
+}
+
+impl<'a, T, U> CommunityTimelineResult<'a, T, U> {
+    #[must_use]
+    pub const fn timeline(&self) -> Option<&TimelineWrapper<'a, T, U>> {
+        match self {
+            Self::Community {
+                ranked_community_timeline: Some(timeline),
+                ..
+            }
+            | Self::Community {
+                community_timeline: Some(timeline),
+                ..
+            } => Some(timeline),
+            _ => None,
+        }
+    }
+}
+
+/// The viewer result for `CommunitiesRankedTimeline`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct RankedCommunitiesViewer<'a, T, U> {
+    #[serde(borrow)]
+    pub ranked_communities_timeline: TimelineWrapper<'a, T, U>,
+}
+
+/// A community membership roster assembled from member and moderator slices.
+///
+/// Users are identified by the provided ID function, and each user is assigned the most privileged
+/// role they have been seen with.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Roster<'u, U> {
+    users: BTreeMap<u64, (Role, &'u U)>,
+}
+
+impl<U> Default for Roster<'_, U> {
+    fn default() -> Self {
+        Self {
+            users: BTreeMap::new(),
+        }
+    }
+}
+
+impl<'u, U> Roster<'u, U> {
+    pub fn add_user(&mut self, id: u64, role: Role, user: &'u U) {
+        self.users
+            .entry(id)
+            .and_modify(|(existing_role, existing_user)| {
+                if role > *existing_role {
+                    *existing_role = role;
+                    *existing_user = user;
+                }
+            })
+            .or_insert((role, user));
+    }
+
+    /// Add every user in a slice with the given role.
+    pub fn add_slice<F: Fn(&U) -> u64>(&mut self, role: Role, slice: &'u Slice<'_, U>, id: F) {
+        for user in slice.users() {
+            self.add_user(id(user), role, user);
+        }
+    }
+
+    /// Add the users from a members or moderators slice response.
+    pub fn add_members_result<F: Fn(&U) -> u64>(
+        &mut self,
+        result: &'u CommunityMembersResult<'_, U>,
+        id: F,
+    ) {
+        if let CommunityMembersResult::Community {
+            members_slice,
+            moderators_slice,
+        } = result
+        {
+            if let Some(slice) = members_slice {
+                self.add_slice(Role::Member, slice, &id);
+            }
+
+            if let Some(slice) = moderators_slice {
+                self.add_slice(Role::Moderator, slice, &id);
+            }
+        }
+    }
+
+    /// Add the admin of a community.
+    pub fn add_admin<F: Fn(&U) -> u64>(&mut self, community: &'u Community<'_, U>, id: F) {
+        if let Some(admin) = &community.admin_results.result {
+            self.add_user(id(admin), Role::Admin, admin);
+        }
+    }
+
+    #[must_use]
+    pub fn role(&self, id: u64) -> Option<Role> {
+        self.users.get(&id).map(|(role, _)| *role)
+    }
+
+    /// All users in ID order.
+    pub fn users(&self) -> impl Iterator<Item = (u64, Role, &'u U)> + '_ {
+        self.users
+            .iter()
+            .map(|(id, (role, user))| (*id, *role, *user))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::graphql::{properties::CursorType, user::User};
+
+    const COMMUNITIES_2024_08_01: &str =
+        include_str!("../../../../../examples/graphql/communities-2024-08-01.ndjson");
+    const COMMUNITIES_2025_06_01: &str =
+        include_str!("../../../../../examples/graphql/communities-2025-06-01.ndjson");
+    // A synthetic response (not a capture), with one tweet and top and bottom cursors.
+    const COMMUNITY_TWEETS_TIMELINE: &str =
+        include_str!("../../../../../examples/graphql/community-tweets-timeline-synthetic.json");
+
+    #[derive(Debug, serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct CommunityTweetsTimelineResponse<'a> {
+        #[serde(borrow)]
+        data: CommunityTweetsTimelineData<'a>,
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct CommunityTweetsTimelineData<'a> {
+        #[serde(rename = "communityResults", borrow)]
+        community_results: crate::model::graphql::ResultWrapper<
+            super::CommunityTimelineResult<'a, serde_json::Value, User<'a>>,
+        >,
+    }
+
+    #[test]
+    fn deserialize_examples_2024_08_01() {
+        for (i, line) in COMMUNITIES_2024_08_01.split('\n').enumerate() {
+            if let Err(error) = serde_json::from_str::<super::CommunityResult<'_, User<'_>>>(line) {
+                panic!("Error at line {}: {:?}", i + 1, error);
+            }
+        }
+    }
+
+    #[test]
+    fn deserialize_examples_2025_06_01() {
+        for (i, line) in COMMUNITIES_2025_06_01.split('\n').enumerate() {
+            if let Err(error) = serde_json::from_str::<super::CommunityResult<'_, User<'_>>>(line) {
+                panic!("Error at line {}: {:?}", i + 1, error);
+            }
+        }
+    }
+
+    #[test]
+    fn deserialize_community_tweets_timeline() {
+        let response =
+            serde_json::from_str::<CommunityTweetsTimelineResponse<'_>>(COMMUNITY_TWEETS_TIMELINE)
+                .unwrap();
+        let result = response.data.community_results.result.unwrap();
+        let timeline = &result.timeline().unwrap().timeline;
+
+        assert_eq!(
+            timeline
+                .tweets()
+                .map(|tweet| tweet["rest_id"].as_str())
+                .collect::<Vec<_>>(),
+            vec![Some("1930000000000000001")]
+        );
+        assert_eq!(
+            timeline.cursor(CursorType::Bottom),
+            Some("DAABCgABGsoAAAAAAAEKAAIaygAAAAAAAAAA")
+        );
+        assert!(timeline.response_objects.is_some());
+    }
+
+    #[test]
+    fn deserialize_ranked_communities_viewer() {
+        // A synthetic timeline (not a capture) wrapping a captured community.
+        let community = COMMUNITIES_2025_06_01.lines().next().unwrap();
+        let json = format!(
+            concat!(
+                r#"{{"ranked_communities_timeline":{{"timeline":{{"instructions":[{{"type":"TimelineAddEntries","entries":["#,
+                r#"{{"entryId":"community-1603892846314295296","sortIndex":"1","content":{{"entryType":"TimelineTimelineItem","#,
+                r#""__typename":"TimelineTimelineItem","itemContent":{{"itemType":"TimelineCommunity","__typename":"TimelineCommunity","#,
+                r#""community_results":{{"result":{}}}}}}}}},"#,
+                r#"{{"entryId":"cursor-bottom-0","sortIndex":"0","content":{{"entryType":"TimelineTimelineCursor","#,
+                r#""__typename":"TimelineTimelineCursor","value":"abc","cursorType":"Bottom"}}}}]}}]}}}}}}"#
+            ),
+            community
+        );
+
+        let viewer = serde_json::from_str::<
+            super::RankedCommunitiesViewer<'_, serde_json::Value, User<'_>>,
+        >(&json)
+        .unwrap();
+        let timeline = &viewer.ranked_communities_timeline.timeline;
+
+        assert_eq!(
+            timeline
+                .communities()
+                .map(|community| match community {
+                    super::CommunityResult::Community { community } => Some(community.id),
+                    super::CommunityResult::CommunityUnavailable {} => None,
+                })
+                .collect::<Vec<_>>(),
+            vec![Some(1_603_892_846_314_295_296)]
+        );
+        assert_eq!(timeline.cursor(CursorType::Bottom), Some("abc"));
+    }
+
+    #[test]
+    fn build_roster_from_slices() {
+        let members = concat!(
+            r#"{"__typename":"Community","members_slice":{"items_results":["#,
+            r#"{"result":{"__typename":"User","rest_id":"1","legacy":{"screen_name":"a","name":"A"}}},"#,
+            r#"{"result":{"__typename":"User","rest_id":"2","legacy":{"screen_name":"b","name":"B"}}}],"#,
+            r#""slice_info":{"next_cursor":"abc"}}}"#
+        );
+        let moderators = concat!(
+            r#"{"__typename":"Community","moderators_slice":{"items_results":["#,
+            r#"{"result":{"__typename":"User","rest_id":"2","legacy":{"screen_name":"b","name":"B"}}}],"#,
+            r#""slice_info":{}}}"#
+        );
+
+        let members =
+            serde_json::from_str::<super::CommunityMembersResult<'_, User<'_>>>(members).unwrap();
+        let moderators =
+            serde_json::from_str::<super::CommunityMembersResult<'_, User<'_>>>(moderators)
+                .unwrap();
+
+        let mut roster = super::Roster::default();
+        roster.add_members_result(&moderators, |user| user.rest_id);
+        roster.add_members_result(&members, |user| user.rest_id);
+
+        assert_eq!(roster.len(), 2);
+        assert_eq!(roster.role(1), Some(super::Role::Member));
+        assert_eq!(roster.role(2), Some(super::Role::Moderator));
+        assert_eq!(roster.role(3), None);
+    }
+}
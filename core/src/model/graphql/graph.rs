@@ -0,0 +1,314 @@
+//! Follow and engagement edges from user list timelines (`Followers`, `Following`,
+//! `BlueVerifiedFollowers`, `FollowersYouKnow`, `Favoriters`, and `Retweeters`).
+use crate::model::graphql::{timeline::instruction::Timeline, user::UserResult};
+use chrono::{DateTime, Utc};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+/// The relation from the source user to the target user.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
+)]
+pub enum Relation {
+    Follows,
+    /// The source user liked this tweet by the target user.
+    Liked {
+        tweet_id: u64,
+    },
+    /// The source user retweeted this tweet by the target user.
+    Retweeted {
+        tweet_id: u64,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Edge<'a> {
+    pub source: u64,
+    pub target: u64,
+    pub relation: Relation,
+    pub observed_at: DateTime<Utc>,
+    /// The cursor used to request the page the edge was observed on (if any).
+    #[serde(borrow)]
+    pub page_cursor: Option<Cow<'a, str>>,
+}
+
+/// The subject of a user list timeline request.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Page {
+    Followers { user_id: u64 },
+    Following { user_id: u64 },
+    BlueVerifiedFollowers { user_id: u64 },
+    FollowersYouKnow { user_id: u64 },
+    Favoriters { tweet_id: u64, author_id: u64 },
+    Retweeters { tweet_id: u64, author_id: u64 },
+}
+
+impl Page {
+    /// The page for a request, given its operation name and variables.
+    ///
+    /// The variables for `Favoriters` and `Retweeters` only identify the tweet, so the author must be
+    /// provided for these requests. Returns `None` for other operations, for invalid variables, or
+    /// if the author is needed but not provided.
+    #[must_use]
+    pub fn from_request(
+        name: &str,
+        variables: &serde_json::Value,
+        author_id: Option<u64>,
+    ) -> Option<Self> {
+        let id = |key: &str| variables.get(key)?.as_str()?.parse::<u64>().ok();
+
+        match name {
+            "Followers" => id("userId").map(|user_id| Self::Followers { user_id }),
+            "Following" => id("userId").map(|user_id| Self::Following { user_id }),
+            "BlueVerifiedFollowers" => {
+                id("userId").map(|user_id| Self::BlueVerifiedFollowers { user_id })
+            }
+            "FollowersYouKnow" => id("userId").map(|user_id| Self::FollowersYouKnow { user_id }),
+            "Favoriters" => {
+                id("tweetId")
+                    .zip(author_id)
+                    .map(|(tweet_id, author_id)| Self::Favoriters {
+                        tweet_id,
+                        author_id,
+                    })
+            }
+            "Retweeters" => {
+                id("tweetId")
+                    .zip(author_id)
+                    .map(|(tweet_id, author_id)| Self::Retweeters {
+                        tweet_id,
+                        author_id,
+                    })
+            }
+            _ => None,
+        }
+    }
+
+    /// The edge between the subject and a user listed on the page.
+    #[must_use]
+    pub const fn edge_endpoints(self, listed_user_id: u64) -> (u64, u64, Relation) {
+        match self {
+            Self::Followers { user_id }
+            | Self::BlueVerifiedFollowers { user_id }
+            | Self::FollowersYouKnow { user_id } => (listed_user_id, user_id, Relation::Follows),
+            Self::Following { user_id } => (user_id, listed_user_id, Relation::Follows),
+            Self::Favoriters {
+                tweet_id,
+                author_id,
+            } => (listed_user_id, author_id, Relation::Liked { tweet_id }),
+            Self::Retweeters {
+                tweet_id,
+                author_id,
+            } => (listed_user_id, author_id, Relation::Retweeted { tweet_id }),
+        }
+    }
+
+    /// Extract edges for all available users in the page's timeline.
+    ///
+    /// The `user_id` function is used to identify listed users.
+    pub fn edges<'a, 'b, T, U, F: Fn(&U) -> u64 + 'b>(
+        self,
+        timeline: &'b Timeline<'a, T, U>,
+        observed_at: DateTime<Utc>,
+        page_cursor: Option<&'a str>,
+        user_id: F,
+    ) -> impl Iterator<Item = Edge<'a>> + 'b
+    where
+        'a: 'b,
+    {
+        timeline.users().filter_map(move |result| match result {
+            UserResult::User { user } => {
+                let (source, target, relation) = self.edge_endpoints(user_id(user));
+
+                Some(Edge {
+                    source,
+                    target,
+                    relation,
+                    observed_at,
+                    page_cursor: page_cursor.map(Cow::Borrowed),
+                })
+            }
+            UserResult::UserUnavailable { .. } => None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Observations {
+    pub first_observed_at: DateTime<Utc>,
+    pub last_observed_at: DateTime<Utc>,
+    pub count: usize,
+}
+
+/// Deduplicated edges across any number of pages and snapshots.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Graph {
+    edges: BTreeMap<(u64, u64, Relation), Observations>,
+}
+
+impl Graph {
+    pub fn add(&mut self, edge: &Edge<'_>) {
+        self.edges
+            .entry((edge.source, edge.target, edge.relation))
+            .and_modify(|observations| {
+                observations.first_observed_at =
+                    observations.first_observed_at.min(edge.observed_at);
+                observations.last_observed_at = observations.last_observed_at.max(edge.observed_at);
+                observations.count += 1;
+            })
+            .or_insert(Observations {
+                first_observed_at: edge.observed_at,
+                last_observed_at: edge.observed_at,
+                count: 1,
+            });
+    }
+
+    #[must_use]
+    pub fn get(&self, source: u64, target: u64, relation: Relation) -> Option<&Observations> {
+        self.edges.get(&(source, target, relation))
+    }
+
+    /// All edges, ordered by source, target, and relation.
+    pub fn iter(&self) -> impl Iterator<Item = ((u64, u64, Relation), &Observations)> {
+        self.edges
+            .iter()
+            .map(|(key, observations)| (*key, observations))
+    }
+
+    /// Users observed following the given user.
+    pub fn followers(&self, user_id: u64) -> impl Iterator<Item = u64> {
+        self.edges
+            .keys()
+            .filter(move |(_, target, relation)| {
+                *target == user_id && *relation == Relation::Follows
+            })
+            .map(|(source, _, _)| *source)
+    }
+
+    /// Users the given user was observed following.
+    pub fn following(&self, user_id: u64) -> impl Iterator<Item = u64> {
+        self.edges
+            .range((user_id, 0, Relation::Follows)..=(user_id, u64::MAX, Relation::Follows))
+            .filter(|((_, _, relation), _)| *relation == Relation::Follows)
+            .map(|((_, target, _), _)| *target)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+impl<'a> Extend<Edge<'a>> for Graph {
+    fn extend<I: IntoIterator<Item = Edge<'a>>>(&mut self, iter: I) {
+        for edge in iter {
+            self.add(&edge);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Graph, Page, Relation};
+    use crate::model::graphql::{timeline::instruction::Timeline, user::User};
+    use chrono::DateTime;
+
+    const FOLLOWERS: &str = concat!(
+        r#"{"instructions":[{"type":"TimelineAddEntries","entries":[{"entryId":"user-12","#,
+        r#""sortIndex":"2","content":{"entryType":"TimelineTimelineItem","#,
+        r#""__typename":"TimelineTimelineItem","itemContent":{"itemType":"TimelineUser","#,
+        r#""__typename":"TimelineUser","user_results":{"result":{"__typename":"User","#,
+        r#""rest_id":"12","legacy":{"screen_name":"jack","name":"jack"}}},"#,
+        r#""userDisplayType":"User"}}}]}]}"#
+    );
+
+    #[test]
+    fn extract_and_accumulate_edges() {
+        let timeline =
+            serde_json::from_str::<Timeline<'_, serde_json::Value, User<'_>>>(FOLLOWERS).unwrap();
+
+        let t0 = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let t1 = DateTime::from_timestamp(1_700_086_400, 0).unwrap();
+
+        let page = Page::Followers { user_id: 783_214 };
+        let mut graph = Graph::default();
+
+        graph.extend(page.edges(&timeline, t1, Some("1|2"), |user| user.rest_id));
+        graph.extend(page.edges(&timeline, t0, None, |user| user.rest_id));
+
+        let observations = graph.get(12, 783_214, Relation::Follows).unwrap();
+
+        assert_eq!(graph.len(), 1);
+        assert_eq!(observations.first_observed_at, t0);
+        assert_eq!(observations.last_observed_at, t1);
+        assert_eq!(observations.count, 2);
+        assert_eq!(graph.followers(783_214).collect::<Vec<_>>(), vec![12]);
+        assert_eq!(graph.following(12).collect::<Vec<_>>(), vec![783_214]);
+
+        let page = Page::Favoriters {
+            tweet_id: 20,
+            author_id: 783_214,
+        };
+        let edge = page
+            .edges(&timeline, t0, None, |user| user.rest_id)
+            .next()
+            .unwrap();
+
+        assert_eq!(edge.source, 12);
+        assert_eq!(edge.relation, Relation::Liked { tweet_id: 20 });
+    }
+
+    #[test]
+    fn page_from_request() {
+        let user_variables = serde_json::json!({
+            "userId": "783214",
+            "count": 20,
+            "includePromotedContent": false
+        });
+        let tweet_variables = serde_json::json!({
+            "tweetId": "20",
+            "count": 20,
+            "includePromotedContent": true
+        });
+
+        assert_eq!(
+            Page::from_request("Followers", &user_variables, None),
+            Some(Page::Followers { user_id: 783_214 })
+        );
+        assert_eq!(
+            Page::from_request("Following", &user_variables, None),
+            Some(Page::Following { user_id: 783_214 })
+        );
+        assert_eq!(
+            Page::from_request("Retweeters", &tweet_variables, Some(783_214)),
+            Some(Page::Retweeters {
+                tweet_id: 20,
+                author_id: 783_214
+            })
+        );
+        assert_eq!(
+            Page::from_request("Favoriters", &tweet_variables, Some(783_214)),
+            Some(Page::Favoriters {
+                tweet_id: 20,
+                author_id: 783_214
+            })
+        );
+        assert_eq!(
+            Page::from_request("Favoriters", &tweet_variables, None),
+            None
+        );
+        assert_eq!(
+            Page::from_request("Followers", &tweet_variables, None),
+            None
+        );
+        assert_eq!(
+            Page::from_request("UserTweets", &user_variables, None),
+            None
+        );
+    }
+}
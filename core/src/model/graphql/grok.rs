@@ -0,0 +1,150 @@
+//! Grok conversations (returned for `GrokConversationItemsByRestId`, `GrokShare`, and
+//! `CreateGrokConversation` requests).
+//!
+//! No captured responses confirm the full field sets for these operations yet, so unknown fields
+//! are ignored rather than denied.
+use crate::model::graphql::ResultWrapper;
+use chrono::{DateTime, Utc, serde::ts_milliseconds};
+use serde_field_attributes::{integer_str, optional_integer_str};
+use std::borrow::Cow;
+
+/// The items of a single conversation (`GrokConversationItemsByRestId`).
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Conversation<'a, T> {
+    #[serde(borrow)]
+    pub items: Vec<Item<'a, T>>,
+    pub cursor: Option<Cow<'a, str>>,
+    pub is_pinned: Option<bool>,
+}
+
+/// A publicly shared conversation (`GrokShare`).
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Share<'a, T> {
+    #[serde(with = "optional_integer_str", default)]
+    pub conversation_id: Option<u64>,
+    #[serde(borrow)]
+    pub items: Vec<Item<'a, T>>,
+}
+
+/// The result of a `CreateGrokConversation` mutation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct CreatedConversation {
+    #[serde(with = "integer_str")]
+    pub conversation_id: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Item<'a, T> {
+    #[serde(rename = "chat_item_id", with = "integer_str")]
+    pub id: u64,
+    #[serde(rename = "created_at_ms", with = "ts_milliseconds")]
+    pub created_at: DateTime<Utc>,
+    pub sender_type: Sender,
+    #[serde(borrow)]
+    pub message: Cow<'a, str>,
+    pub grok_mode: Option<Cow<'a, str>>,
+    pub is_partial: Option<bool>,
+    pub thinking_trace: Option<Cow<'a, str>>,
+    /// Posts cited by the reply.
+    pub post_ids_results: Option<Vec<ResultWrapper<T>>>,
+    pub web_results: Option<Vec<WebResult<'a>>>,
+}
+
+impl<T> Item<'_, T> {
+    #[must_use]
+    pub fn is_reply(&self) -> bool {
+        self.sender_type == Sender::Agent
+    }
+
+    /// Available cited posts.
+    pub fn cited_posts(&self) -> impl Iterator<Item = &T> {
+        self.post_ids_results
+            .iter()
+            .flatten()
+            .filter_map(|wrapper| wrapper.result.as_ref())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Sender {
+    User,
+    Agent,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct WebResult<'a> {
+    #[serde(borrow)]
+    pub url: Cow<'a, str>,
+    pub title: Option<Cow<'a, str>>,
+    pub snippet: Option<Cow<'a, str>>,
+    pub favicon: Option<Cow<'a, str>>,
+}
+
+impl<'a, T> Conversation<'a, T> {
+    /// Grok's replies, in response order.
+    pub fn replies(&self) -> impl Iterator<Item = &Item<'a, T>> {
+        self.items.iter().filter(|item| item.is_reply())
+    }
+}
+
+impl<'a, T> Share<'a, T> {
+    /// Grok's replies, in response order.
+    pub fn replies(&self) -> impl Iterator<Item = &Item<'a, T>> {
+        self.items.iter().filter(|item| item.is_reply())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn deserialize_conversation() {
+        let json = concat!(
+            r#"{"items":[{"chat_item_id":"1900000000000000001","created_at_ms":1741000000000,"#,
+            r#""grok_mode":"Normal","message":"Is this true?","sender_type":"User","#,
+            r#""is_partial":false},{"chat_item_id":"1900000000000000002","#,
+            r#""created_at_ms":1741000005000,"grok_mode":"Normal","message":"Mostly.","#,
+            r#""sender_type":"Agent","is_partial":false,"post_ids_results":[{"result":{"#,
+            r#""rest_id":"1"}},{}],"web_results":[{"url":"https://example.com/","#,
+            r#""title":"Example","snippet":"An example","favicon":"https://example.com/f.ico"}]}],"#,
+            r#""is_pinned":false}"#
+        );
+
+        let conversation =
+            serde_json::from_str::<super::Conversation<'_, serde_json::Value>>(json).unwrap();
+        let replies = conversation.replies().collect::<Vec<_>>();
+
+        assert_eq!(conversation.items.len(), 2);
+        assert_eq!(replies.len(), 1);
+        assert_eq!(replies[0].cited_posts().count(), 1);
+        assert_eq!(replies[0].web_results.as_ref().map(Vec::len), Some(1));
+        assert!(replies[0].created_at > conversation.items[0].created_at);
+    }
+
+    #[test]
+    fn deserialize_share() {
+        let json = concat!(
+            r#"{"conversation_id":"1900000000000000000","items":[{"#,
+            r#""chat_item_id":"1900000000000000001","created_at_ms":1741000000000,"#,
+            r#""message":"Summarize this thread","sender_type":"User"},{"#,
+            r#""chat_item_id":"1900000000000000002","created_at_ms":1741000005000,"#,
+            r#""message":"The thread argues...","sender_type":"Agent","share_link":"x"}]}"#
+        );
+
+        let share = serde_json::from_str::<super::Share<'_, serde_json::Value>>(json).unwrap();
+
+        assert_eq!(share.conversation_id, Some(1_900_000_000_000_000_000));
+        assert_eq!(
+            share.replies().map(|item| item.id).collect::<Vec<_>>(),
+            vec![1_900_000_000_000_000_002]
+        );
+    }
+
+    #[test]
+    fn deserialize_created_conversation() {
+        let json = r#"{"conversation_id":"1900000000000000000"}"#;
+
+        let created = serde_json::from_str::<super::CreatedConversation>(json).unwrap();
+
+        assert_eq!(created.conversation_id, 1_900_000_000_000_000_000);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::model::graphql::{
+    ResultWrapper, community::BannerMedia, timeline::instruction::TimelineWrapper, user::UserResult,
+};
+use chrono::{DateTime, Utc, serde::ts_milliseconds};
+use serde_field_attributes::integer_str;
+use std::borrow::Cow;
+
+/// List metadata (returned for `ListByRestId` requests and in list timeline items).
+///
+/// Viewer-specific fields (for example `following` and `is_member`) describe the account that made
+/// the request. No captured responses confirm the full field set yet, so unknown fields are ignored
+/// rather than denied.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct List<'a, U> {
+    #[serde(rename = "id_str", with = "integer_str")]
+    pub id: u64,
+    #[serde(borrow)]
+    pub name: Cow<'a, str>,
+    pub description: Option<Cow<'a, str>>,
+    pub mode: Mode,
+    pub user_results: ResultWrapper<UserResult<'a, U>>,
+    pub member_count: Option<usize>,
+    pub subscriber_count: Option<usize>,
+    pub custom_banner_media: Option<BannerMedia<'a>>,
+    pub default_banner_media: Option<BannerMedia<'a>>,
+    #[serde(with = "ts_milliseconds")]
+    pub created_at: DateTime<Utc>,
+    pub facepile_urls: Option<Vec<Cow<'a, str>>>,
+    pub followers_context: Option<Cow<'a, str>>,
+    pub members_context: Option<Cow<'a, str>>,
+    pub following: Option<bool>,
+    pub is_member: Option<bool>,
+    pub muting: Option<bool>,
+    pub pinning: Option<bool>,
+    #[serde(rename = "id")]
+    _internal_id: Option<Cow<'a, str>>,
+}
+
+impl<U> List<'_, U> {
+    #[must_use]
+    pub const fn owner(&self) -> Option<&U> {
+        match &self.user_results.result {
+            Some(UserResult::User { user }) => Some(user),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub enum Mode {
+    Public,
+    Private,
+}
+
+/// The list result for `ListMembers`, `ListSubscribers`, and `ListLatestTweetsTimeline`.
+///
+/// Each request populates one of the timelines.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ListTimelines<'a, T, U> {
+    #[serde(borrow)]
+    pub members_timeline: Option<TimelineWrapper<'a, T, U>>,
+    pub subscribers_timeline: Option<TimelineWrapper<'a, T, U>>,
+    pub tweets_timeline: Option<TimelineWrapper<'a, T, U>>,
+}
+
+impl<'a, T, U> ListTimelines<'a, T, U> {
+    pub fn members(&self) -> impl Iterator<Item = &UserResult<'a, U>> {
+        self.members_timeline
+            .iter()
+            .flat_map(|wrapper| wrapper.timeline.users())
+    }
+
+    pub fn subscribers(&self) -> impl Iterator<Item = &UserResult<'a, U>> {
+        self.subscribers_timeline
+            .iter()
+            .flat_map(|wrapper| wrapper.timeline.users())
+    }
+
+    pub fn tweets(&self) -> impl Iterator<Item = &T> {
+        self.tweets_timeline
+            .iter()
+            .flat_map(|wrapper| wrapper.timeline.tweets())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::graphql::user::User;
+
+    #[test]
+    fn deserialize_list() {
+        let json = concat!(
+            r#"{"created_at":1356209183000,"default_banner_media":{"media_info":{"#,
+            r#""color_info":{"palette":[]},"original_img_url":"https://pbs.twimg.com/media/a.jpg","#,
+            r#""original_img_width":1125,"original_img_height":375}},"description":"","#,
+            r#""facepile_urls":[],"following":false,"id":"TGlzdDo1ODY5Mg==","id_str":"58692","#,
+            r#""is_member":false,"member_count":42,"mode":"Public","muting":false,"name":"Tech","#,
+            r#""pinning":false,"subscriber_count":7,"user_results":{"result":{"#,
+            r#""__typename":"User","rest_id":"12","legacy":{"screen_name":"jack","name":"jack"}}}}"#
+        );
+
+        let list = serde_json::from_str::<super::List<'_, User<'_>>>(json).unwrap();
+
+        assert_eq!(list.id, 58692);
+        assert_eq!(list.mode, super::Mode::Public);
+        assert_eq!(list.member_count, Some(42));
+        assert_eq!(list.is_member, Some(false));
+        assert_eq!(list.owner().map(|user| user.rest_id), Some(12));
+    }
+
+    #[test]
+    fn deserialize_list_members() {
+        let json = concat!(
+            r#"{"members_timeline":{"timeline":{"instructions":[{"type":"TimelineAddEntries","#,
+            r#""entries":[{"entryId":"user-12","sortIndex":"2","content":{"#,
+            r#""entryType":"TimelineTimelineItem","__typename":"TimelineTimelineItem","#,
+            r#""itemContent":{"itemType":"TimelineUser","__typename":"TimelineUser","#,
+            r#""user_results":{"result":{"__typename":"User","rest_id":"12","#,
+            r#""legacy":{"screen_name":"jack","name":"jack"}}},"userDisplayType":"User"}}}]}]}}}"#
+        );
+
+        let timelines =
+            serde_json::from_str::<super::ListTimelines<'_, serde_json::Value, User<'_>>>(json)
+                .unwrap();
+
+        assert_eq!(timelines.members().count(), 1);
+        assert_eq!(timelines.subscribers().count(), 0);
+    }
+
+    #[test]
+    fn deserialize_list_timeline_item() {
+        let json = concat!(
+            r#"{"instructions":[{"type":"TimelineAddEntries","entries":[{"entryId":"list-58692","#,
+            r#""sortIndex":"1","content":{"entryType":"TimelineTimelineItem","#,
+            r#""__typename":"TimelineTimelineItem","itemContent":{"itemType":"TimelineTwitterList","#,
+            r#""__typename":"TimelineTwitterList","displayType":"ListWithPin","list":{"#,
+            r#""created_at":1356209183000,"id_str":"58692","mode":"Private","name":"Tech","#,
+            r#""user_results":{"result":{"__typename":"User","rest_id":"12","#,
+            r#""legacy":{"screen_name":"jack","name":"jack"}}}}}}}]}]}"#
+        );
+
+        let timeline = serde_json::from_str::<
+            crate::model::graphql::timeline::instruction::Timeline<'_, serde_json::Value, User<'_>>,
+        >(json)
+        .unwrap();
+
+        assert_eq!(
+            timeline
+                .lists()
+                .map(|list| (list.id, list.mode))
+                .collect::<Vec<_>>(),
+            vec![(58692, super::Mode::Private)]
+        );
+    }
+
+    #[test]
+    fn deserialize_list_timeline_item_with_unknown_fields() {
+        let json = concat!(
+            r#"{"instructions":[{"type":"TimelineAddEntries","entries":[{"entryId":"list-58692","#,
+            r#""sortIndex":"1","content":{"entryType":"TimelineTimelineItem","#,
+            r#""__typename":"TimelineTimelineItem","itemContent":{"itemType":"TimelineTwitterList","#,
+            r#""__typename":"TimelineTwitterList","displayType":"ListWithBanner","list":{"#,
+            r#""created_at":1356209183000,"id_str":"58692","mode":"Private","name":"Tech","#,
+            r#""is_featured":false,"user_results":{"result":{"__typename":"User","rest_id":"12","#,
+            r#""legacy":{"screen_name":"jack","name":"jack"}}}}}}}]}]}"#
+        );
+
+        let timeline = serde_json::from_str::<
+            crate::model::graphql::timeline::instruction::Timeline<'_, serde_json::Value, User<'_>>,
+        >(json)
+        .unwrap();
+
+        assert_eq!(
+            timeline
+                .lists()
+                .map(|list| list.name.as_ref())
+                .collect::<Vec<_>>(),
+            vec!["Tech"]
+        );
+    }
+}
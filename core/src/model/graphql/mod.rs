@@ -1,9 +1,15 @@
 pub mod ads;
+pub mod ai_trend;
 pub mod birdwatch;
+pub mod broadcast;
 pub mod community;
+pub mod graph;
+pub mod grok;
 pub mod image;
+pub mod list;
 pub mod properties;
 pub mod shapes;
+pub mod space;
 pub mod text;
 pub mod timeline;
 pub mod trends;
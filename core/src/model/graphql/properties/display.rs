@@ -5,6 +5,16 @@ pub enum LabelDisplayType {
     InlineHeader,
 }
 
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum ListDisplayType {
+    List,
+    ListWithPin,
+    ListWithSubscribe,
+    /// Any other display type (we have not seen these in captured responses).
+    #[serde(other)]
+    Other,
+}
+
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum ModuleDisplayType {
     Carousel,
@@ -0,0 +1,168 @@
+//! Audio Spaces (returned for `AudioSpaceById` requests).
+//!
+//! No captured responses confirm the full field sets yet, so unknown fields are ignored rather than
+//! denied.
+use crate::model::graphql::{ResultWrapper, community::SliceInfo, user::IdentifiedUserResults};
+use chrono::{DateTime, Utc, serde::ts_milliseconds_option};
+use serde_field_attributes::optional_timestamp_millis_str;
+use std::borrow::Cow;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct AudioSpace<'a, T, U> {
+    #[serde(borrow)]
+    pub metadata: Option<Metadata<'a, T, U>>,
+    pub participants: Option<Participants<'a, U>>,
+    pub sharings: Option<Sharings<'a>>,
+    pub is_subscribed: Option<bool>,
+}
+
+/// Items shared into the Space, which we do not model.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Sharings<'a> {
+    pub items: Vec<serde_json::Value>,
+    #[serde(borrow)]
+    pub slice_info: SliceInfo<'a>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Metadata<'a, T, U> {
+    /// Space IDs are alphanumeric (for example `1YqxoDyNPZBxv`).
+    #[serde(borrow)]
+    pub rest_id: Cow<'a, str>,
+    pub state: State,
+    pub title: Option<Cow<'a, str>>,
+    pub media_key: Option<Cow<'a, str>>,
+    pub creator_results: Option<ResultWrapper<U>>,
+    #[serde(with = "ts_milliseconds_option", default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(with = "ts_milliseconds_option", default)]
+    pub scheduled_start: Option<DateTime<Utc>>,
+    #[serde(with = "ts_milliseconds_option", default)]
+    pub started_at: Option<DateTime<Utc>>,
+    /// Unlike the other timestamps, this is a string.
+    #[serde(with = "optional_timestamp_millis_str", default)]
+    pub ended_at: Option<DateTime<Utc>>,
+    #[serde(with = "ts_milliseconds_option", default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    pub is_space_available_for_replay: Option<bool>,
+    pub is_space_available_for_clipping: Option<bool>,
+    pub total_live_listeners: Option<usize>,
+    pub total_replay_watched: Option<usize>,
+    pub is_locked: Option<bool>,
+    pub is_employee_only: Option<bool>,
+    pub conversation_controls: Option<usize>,
+    pub disallow_join: Option<bool>,
+    pub narrow_cast_space_type: Option<usize>,
+    /// Zero when the replay starts at the beginning of the Space.
+    pub replay_start_time: Option<u64>,
+    pub no_incognito: Option<bool>,
+    pub max_guest_sessions: Option<usize>,
+    /// The tweet the Space was shared in, if any.
+    pub tweet_results: Option<ResultWrapper<T>>,
+}
+
+impl<T, U> Metadata<'_, T, U> {
+    #[must_use]
+    pub const fn creator(&self) -> Option<&U> {
+        match &self.creator_results {
+            Some(ResultWrapper { result: Some(user) }) => Some(user),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn is_replay_available(&self) -> bool {
+        self.is_space_available_for_replay.unwrap_or(false)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum State {
+    NotStarted,
+    PrePublished,
+    Running,
+    Ended,
+    Canceled,
+    TimedOut,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Participants<'a, U> {
+    pub total: usize,
+    #[serde(borrow)]
+    pub admins: Vec<Participant<'a, U>>,
+    pub speakers: Vec<Participant<'a, U>>,
+    pub listeners: Vec<Participant<'a, U>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Participant<'a, U> {
+    #[serde(borrow)]
+    pub periscope_user_id: Option<Cow<'a, str>>,
+    pub twitter_screen_name: Option<Cow<'a, str>>,
+    pub display_name: Option<Cow<'a, str>>,
+    pub avatar_url: Option<Cow<'a, str>>,
+    pub is_verified: Option<bool>,
+    pub is_muted_by_admin: Option<bool>,
+    pub is_muted_by_guest: Option<bool>,
+    pub user_results: Option<IdentifiedUserResults<U>>,
+    /// When the participant joined (only provided for admins and speakers).
+    #[serde(with = "ts_milliseconds_option", default)]
+    pub start: Option<DateTime<Utc>>,
+}
+
+impl<U> Participant<'_, U> {
+    #[must_use]
+    pub const fn user_id(&self) -> Option<u64> {
+        match &self.user_results {
+            Some(results) => results.rest_id,
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::graphql::user::User;
+
+    #[derive(Debug, serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct AudioSpaceResponse<'a> {
+        #[serde(borrow)]
+        data: AudioSpaceData<'a>,
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct AudioSpaceData<'a> {
+        #[serde(rename = "audioSpace", borrow)]
+        audio_space: super::AudioSpace<'a, serde_json::Value, User<'a>>,
+    }
+
+    #[test]
+    fn deserialize_audio_space() {
+        // A synthetic response (not a capture) with the full `AudioSpaceById` field set.
+        let json = include_str!("../../../../examples/graphql/audio-space-synthetic.json");
+
+        let response = serde_json::from_str::<AudioSpaceResponse<'_>>(json).unwrap();
+        let space = response.data.audio_space;
+        let metadata = space.metadata.unwrap();
+        let participants = space.participants.unwrap();
+
+        assert_eq!(metadata.state, super::State::Ended);
+        assert!(metadata.is_replay_available());
+        assert_eq!(metadata.creator().map(|user| user.rest_id), Some(12));
+        assert!(metadata.ended_at > metadata.started_at);
+        assert_eq!(
+            metadata
+                .tweet_results
+                .and_then(|results| results.result)
+                .and_then(|tweet| tweet["rest_id"].as_str().map(str::to_string)),
+            Some("1700000000000000001".to_string())
+        );
+        assert_eq!(participants.admins[0].user_id(), Some(12));
+        assert_eq!(participants.admins[0].start, metadata.started_at);
+        assert_eq!(participants.listeners[0].start, None);
+        assert_eq!(space.is_subscribed, Some(false));
+    }
+}
@@ -0,0 +1,245 @@
+use crate::model::graphql::{
+    community::CommunityResult,
+    list::List,
+    properties::{CursorType, TimelineDirection, display::ModuleDisplayType},
+    timeline::{Item, ItemContent, ModuleItem, client, item},
+    user::UserResult,
+};
+
+/// A timeline as returned in GraphQL responses.
+///
+/// No captured responses confirm the full field sets of the timeline types yet, so unknown fields
+/// are ignored rather than denied.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Timeline<'a, T, U> {
+    #[serde(borrow)]
+    pub instructions: Vec<Instruction<'a, T, U>>,
+    pub metadata: Option<TimelineMetadata>,
+    /// Feedback actions and other response objects, which we do not model.
+    #[serde(rename = "responseObjects")]
+    pub response_objects: Option<serde_json::Value>,
+}
+
+/// These are interface elements we don't care about.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct TimelineMetadata {}
+
+/// The `{"timeline": ...}` object that most timeline responses nest their timeline in.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct TimelineWrapper<'a, T, U> {
+    #[serde(borrow)]
+    pub timeline: Timeline<'a, T, U>,
+}
+
+/// Instructions that only affect the display of the timeline do not preserve their fields.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "type")]
+pub enum Instruction<'a, T, U> {
+    TimelineAddEntries {
+        #[serde(borrow)]
+        entries: Vec<Entry<'a, T, U>>,
+    },
+    TimelineAddToModule {
+        #[serde(rename = "moduleItems")]
+        module_items: Vec<ModuleItem<'a, T, U>>,
+        #[serde(rename = "moduleEntryId")]
+        module_entry_id: &'a str,
+        prepend: Option<bool>,
+    },
+    TimelinePinEntry {
+        entry: Entry<'a, T, U>,
+    },
+    TimelineReplaceEntry {
+        entry_id_to_replace: &'a str,
+        entry: Entry<'a, T, U>,
+    },
+    TimelineTerminateTimeline {
+        direction: TimelineDirection,
+    },
+    TimelineClearCache {},
+    TimelineClearEntriesUnreadState {},
+    TimelineMarkEntriesUnreadGreaterThanSortIndex {},
+    TimelineShowAlert {},
+    TimelineShowCover {},
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Entry<'a, T, U> {
+    #[serde(rename = "entryId")]
+    pub entry_id: &'a str,
+    #[serde(rename = "sortIndex")]
+    pub sort_index: &'a str,
+    #[serde(borrow)]
+    pub content: EntryContent<'a, T, U>,
+}
+
+// Entries are read in place from the parsed timeline, so the variant size difference does not cost
+// us any copying.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "entryType")]
+pub enum EntryContent<'a, T, U> {
+    #[serde(rename = "TimelineTimelineItem")]
+    Item {
+        #[serde(rename = "__typename")]
+        typename: &'a str,
+        #[serde(flatten, borrow)]
+        item: Item<'a, T, U>,
+    },
+    #[serde(rename = "TimelineTimelineCursor")]
+    Cursor {
+        #[serde(rename = "__typename")]
+        typename: &'a str,
+        #[serde(flatten)]
+        cursor: item::Cursor<'a>,
+    },
+    #[serde(rename = "TimelineTimelineModule")]
+    Module {
+        #[serde(rename = "__typename")]
+        typename: &'a str,
+        items: Option<Vec<ModuleItem<'a, T, U>>>,
+        #[serde(rename = "displayType")]
+        display_type: ModuleDisplayType,
+        header: Option<ModuleHeader>,
+        footer: Option<ModuleFooter>,
+        metadata: Option<ModuleMetadata>,
+        #[serde(rename = "clientEventInfo")]
+        client_event_info: Option<client::event::ClientEventInfo<'a>>,
+        #[serde(rename = "feedbackInfo")]
+        feedback_info: Option<client::feedback::FeedbackInfo<'a>>,
+    },
+}
+
+/// These are interface elements we don't care about.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ModuleHeader {}
+
+/// These are interface elements we don't care about.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ModuleFooter {}
+
+/// These are interface elements we don't care about.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ModuleMetadata {}
+
+impl<'a, T, U> Timeline<'a, T, U> {
+    /// All entries that are added, pinned, or used as replacements, in instruction order.
+    pub fn entries(&self) -> impl Iterator<Item = &Entry<'a, T, U>> {
+        self.instructions
+            .iter()
+            .flat_map(|instruction| match instruction {
+                Instruction::TimelineAddEntries { entries } => entries.as_slice(),
+                Instruction::TimelinePinEntry { entry }
+                | Instruction::TimelineReplaceEntry { entry, .. } => std::slice::from_ref(entry),
+                _ => &[],
+            })
+    }
+
+    /// All items, including items in modules (items added to existing modules come last).
+    pub fn items(&self) -> impl Iterator<Item = &Item<'a, T, U>> {
+        self.entries().flat_map(Entry::items).chain(
+            self.instructions
+                .iter()
+                .flat_map(|instruction| match instruction {
+                    Instruction::TimelineAddToModule { module_items, .. } => {
+                        module_items.as_slice()
+                    }
+                    _ => &[],
+                })
+                .map(|module_item| &module_item.item),
+        )
+    }
+
+    /// The value of the first cursor entry of the given type.
+    #[must_use]
+    pub fn cursor(&self, cursor_type: CursorType) -> Option<&str> {
+        self.entries().find_map(|entry| match &entry.content {
+            EntryContent::Cursor { cursor, .. } if cursor.cursor_type == cursor_type => {
+                Some(cursor.value.as_ref())
+            }
+            _ => None,
+        })
+    }
+
+    /// All available tweet results.
+    pub fn tweets(&self) -> impl Iterator<Item = &T> {
+        self.items().filter_map(|item| match &item.item_content {
+            ItemContent::Tweet { tweet, .. } => tweet.tweet_results.result.as_ref(),
+            _ => None,
+        })
+    }
+
+    /// All available community results.
+    pub fn communities(&self) -> impl Iterator<Item = &CommunityResult<'a, U>> {
+        self.items().filter_map(|item| match &item.item_content {
+            ItemContent::Community {
+                community_results, ..
+            } => community_results.result.as_ref(),
+            _ => None,
+        })
+    }
+
+    /// All available lists.
+    pub fn lists(&self) -> impl Iterator<Item = &List<'a, U>> {
+        self.items().filter_map(|item| match &item.item_content {
+            ItemContent::TwitterList { list, .. } => Some(list.as_ref()),
+            _ => None,
+        })
+    }
+
+    /// All available user results.
+    pub fn users(&self) -> impl Iterator<Item = &UserResult<'a, U>> {
+        self.items().filter_map(|item| match &item.item_content {
+            ItemContent::User { user, .. } => user.user_results.result.as_ref(),
+            _ => None,
+        })
+    }
+}
+
+impl<'a, T, U> Entry<'a, T, U> {
+    /// The item for an item entry, or the items for a module entry.
+    pub fn items(&self) -> impl Iterator<Item = &Item<'a, T, U>> {
+        let (item, module_items) = match &self.content {
+            EntryContent::Item { item, .. } => (Some(item), None),
+            EntryContent::Module { items, .. } => (None, items.as_ref()),
+            EntryContent::Cursor { .. } => (None, None),
+        };
+
+        item.into_iter().chain(
+            module_items
+                .into_iter()
+                .flatten()
+                .map(|module_item| &module_item.item),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::graphql::{properties::CursorType, user::User};
+
+    type Timeline<'a> = super::Timeline<'a, serde_json::Value, User<'a>>;
+
+    #[test]
+    fn deserialize_user_timeline() {
+        let json = concat!(
+            r#"{"instructions":[{"type":"TimelineClearCache"},{"type":"TimelineTerminateTimeline","#,
+            r#""direction":"Top"},{"type":"TimelineAddEntries","entries":[{"entryId":"user-12","#,
+            r#""sortIndex":"2","content":{"entryType":"TimelineTimelineItem","#,
+            r#""__typename":"TimelineTimelineItem","itemContent":{"itemType":"TimelineUser","#,
+            r#""__typename":"TimelineUser","user_results":{"result":{"__typename":"User","#,
+            r#""rest_id":"12","legacy":{"screen_name":"jack","name":"jack"}}},"#,
+            r#""userDisplayType":"User"}}},{"entryId":"cursor-bottom-1","sortIndex":"1","#,
+            r#""content":{"entryType":"TimelineTimelineCursor","#,
+            r#""__typename":"TimelineTimelineCursor","value":"1|2","cursorType":"Bottom"}}]}],"#,
+            r#""metadata":{}}"#
+        );
+
+        let timeline = serde_json::from_str::<Timeline<'_>>(json).unwrap();
+
+        assert_eq!(timeline.entries().count(), 2);
+        assert_eq!(timeline.users().count(), 1);
+        assert_eq!(timeline.cursor(CursorType::Bottom), Some("1|2"));
+        assert_eq!(timeline.cursor(CursorType::Top), None);
+    }
+}
@@ -18,6 +18,8 @@ pub struct Cursor<'a> {
     pub value: Cow<'a, str>,
     #[serde(rename = "displayTreatment", borrow)]
     display_treatment: Option<DisplayTreatment<'a>>,
+    #[serde(rename = "stopOnEmptyResponse")]
+    pub stop_on_empty_response: Option<bool>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
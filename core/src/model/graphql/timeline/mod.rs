@@ -4,7 +4,7 @@ use crate::model::graphql::{
     image::{Image, OriginalImage},
     properties::{
         TombstoneInfo,
-        display::{LabelDisplayType, PivotDisplayType, TombstoneDisplayType},
+        display::{LabelDisplayType, ListDisplayType, PivotDisplayType, TombstoneDisplayType},
     },
     trends::TrendMetadata,
 };
@@ -12,6 +12,7 @@ use std::borrow::Cow;
 
 pub mod client;
 pub mod context;
+pub mod instruction;
 pub mod item;
 pub mod trends;
 
@@ -122,8 +123,13 @@ pub enum ItemContent<'a, T, U> {
     #[serde(rename = "TimelineSpelling")]
     Spelling,
     #[serde(rename = "TimelineTwitterList")]
-    // TODO: Support access for user results
-    TwitterList,
+    TwitterList {
+        #[serde(rename = "__typename")]
+        typename: &'a str,
+        list: Box<crate::model::graphql::list::List<'a, U>>,
+        #[serde(rename = "displayType")]
+        display_type: ListDisplayType,
+    },
     #[serde(rename = "TimelineScoreEventCard")]
     // TODO: Support access for user results
     ScoreEventCard,
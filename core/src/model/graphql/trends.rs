@@ -1,4 +1,7 @@
+use crate::model::graphql::timeline::ItemContent;
+use chrono::{DateTime, Utc};
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 #[serde(deny_unknown_fields)]
@@ -9,6 +12,217 @@ pub struct TrendMetadata<'a> {
     pub url: Option<crate::model::url::Url<'a>>,
 }
 
+impl TrendMetadata<'_> {
+    /// The post count shown in the meta description (for example `"12.5K posts"`).
+    ///
+    /// Abbreviated counts are expanded, so the result is only as precise as the displayed value.
+    #[must_use]
+    pub fn post_count(&self) -> Option<u64> {
+        self.meta_description.as_deref().and_then(parse_post_count)
+    }
+}
+
+/// The trends displayed in a single trends timeline response.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct TrendsSnapshot<'a> {
+    pub timestamp: DateTime<Utc>,
+    /// The location or guide (for example an explore tab) the trends were requested for.
+    #[serde(borrow)]
+    pub location: Option<Cow<'a, str>>,
+    pub trends: Vec<SnapshotTrend<'a>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct SnapshotTrend<'a> {
+    /// The displayed rank, or the one-based position in the snapshot if no rank is displayed.
+    pub rank: usize,
+    #[serde(borrow)]
+    pub name: Cow<'a, str>,
+    pub description: Option<Cow<'a, str>>,
+    pub domain_context: Option<Cow<'a, str>>,
+    pub is_ai_trend: bool,
+    pub grouped_trends: Vec<Cow<'a, str>>,
+    pub promoted: bool,
+    pub post_count: Option<u64>,
+}
+
+impl<'a> TrendsSnapshot<'a> {
+    /// Collect the trends from timeline items in display order, ignoring other kinds of items.
+    pub fn from_items<'b, T: 'b, U: 'b, I: IntoIterator<Item = &'b ItemContent<'a, T, U>>>(
+        timestamp: DateTime<Utc>,
+        location: Option<Cow<'a, str>>,
+        items: I,
+    ) -> Self
+    where
+        'a: 'b,
+    {
+        let trends = items
+            .into_iter()
+            .filter(|item| matches!(item, ItemContent::Trend { .. }))
+            .enumerate()
+            .filter_map(|(i, item)| SnapshotTrend::from_item(i + 1, item))
+            .collect();
+
+        Self {
+            timestamp,
+            location,
+            trends,
+        }
+    }
+}
+
+impl<'a> SnapshotTrend<'a> {
+    fn from_item<T, U>(position: usize, item: &ItemContent<'a, T, U>) -> Option<Self> {
+        match item {
+            ItemContent::Trend {
+                name,
+                description,
+                is_ai_trend,
+                trend_metadata,
+                grouped_trends,
+                rank,
+                promoted_metadata,
+                ..
+            } => Some(Self {
+                rank: rank
+                    .as_deref()
+                    .and_then(|rank| rank.parse().ok())
+                    .unwrap_or(position),
+                name: name.clone(),
+                description: description.clone(),
+                domain_context: trend_metadata.domain_context.clone(),
+                is_ai_trend: is_ai_trend.unwrap_or(false),
+                grouped_trends: grouped_trends
+                    .iter()
+                    .flatten()
+                    .map(|trend| trend.name.clone())
+                    .collect(),
+                promoted: promoted_metadata.is_some(),
+                post_count: trend_metadata.post_count(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// The observations of a single trend (identified by name) across a sequence of snapshots.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct TrendSeries {
+    pub name: String,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    /// The best (lowest) rank observed.
+    pub peak_rank: usize,
+    pub observations: Vec<TrendObservation>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct TrendObservation {
+    pub timestamp: DateTime<Utc>,
+    pub rank: usize,
+    pub post_count: Option<u64>,
+}
+
+impl TrendSeries {
+    fn new(name: String, observation: TrendObservation) -> Self {
+        Self {
+            name,
+            first_seen: observation.timestamp,
+            last_seen: observation.timestamp,
+            peak_rank: observation.rank,
+            observations: vec![observation],
+        }
+    }
+
+    fn add(&mut self, observation: TrendObservation) {
+        self.first_seen = self.first_seen.min(observation.timestamp);
+        self.last_seen = self.last_seen.max(observation.timestamp);
+        self.peak_rank = self.peak_rank.min(observation.rank);
+
+        let index = self
+            .observations
+            .partition_point(|existing| existing.timestamp <= observation.timestamp);
+        self.observations.insert(index, observation);
+    }
+
+    /// Merge snapshots into one series per trend name.
+    ///
+    /// Snapshots may be provided in any order. Promoted trends are skipped, since their position
+    /// is purchased. If a trend appears more than once in a snapshot, only its best rank is kept.
+    pub fn from_snapshots<'a, 'b: 'a, I: IntoIterator<Item = &'a TrendsSnapshot<'b>>>(
+        snapshots: I,
+    ) -> BTreeMap<String, Self> {
+        let mut series = BTreeMap::<String, Self>::new();
+
+        for snapshot in snapshots {
+            let mut best = BTreeMap::<&str, TrendObservation>::new();
+
+            for trend in snapshot.trends.iter().filter(|trend| !trend.promoted) {
+                let observation = TrendObservation {
+                    timestamp: snapshot.timestamp,
+                    rank: trend.rank,
+                    post_count: trend.post_count,
+                };
+
+                best.entry(trend.name.as_ref())
+                    .and_modify(|existing| {
+                        if observation.rank < existing.rank {
+                            *existing = observation;
+                        }
+                    })
+                    .or_insert(observation);
+            }
+
+            for (name, observation) in best {
+                match series.get_mut(name) {
+                    Some(existing) => existing.add(observation),
+                    None => {
+                        series.insert(name.to_string(), Self::new(name.to_string(), observation));
+                    }
+                }
+            }
+        }
+
+        series
+    }
+}
+
+/// Parse a displayed count like `"1,234 posts"`, `"12.5K posts"`, or `"2M Tweets"`.
+fn parse_post_count(input: &str) -> Option<u64> {
+    let mut parts = input.split_whitespace();
+    let count = parts.next()?;
+    let unit = parts.next()?.to_lowercase();
+
+    if !(unit.starts_with("post") || unit.starts_with("tweet")) {
+        return None;
+    }
+
+    let (number, multiplier) = match count.chars().last()? {
+        'K' | 'k' => (&count[..count.len() - 1], 1_000),
+        'M' | 'm' => (&count[..count.len() - 1], 1_000_000),
+        'B' | 'b' => (&count[..count.len() - 1], 1_000_000_000),
+        _ => (count, 1),
+    };
+
+    let number = number.replace(',', "");
+    let (whole, fraction) = number.split_once('.').unwrap_or((&number, ""));
+
+    if whole.is_empty() || !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let whole = whole.parse::<u64>().ok()?;
+    let fraction_value = if fraction.is_empty() {
+        0
+    } else {
+        let divisor = 10_u64.checked_pow(u32::try_from(fraction.len()).ok()?)?;
+
+        fraction.parse::<u64>().ok()?.checked_mul(multiplier)? / divisor
+    };
+
+    whole.checked_mul(multiplier)?.checked_add(fraction_value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -33,4 +247,62 @@ mod tests {
             value
         );
     }
+
+    #[test]
+    fn parses_post_counts() {
+        assert_eq!(parse_post_count("1,234 posts"), Some(1_234));
+        assert_eq!(parse_post_count("12.5K posts"), Some(12_500));
+        assert_eq!(parse_post_count("2M Tweets"), Some(2_000_000));
+        assert_eq!(parse_post_count("1.25B posts"), Some(1_250_000_000));
+        assert_eq!(parse_post_count("Trending with #foo"), None);
+        assert_eq!(parse_post_count("K posts"), None);
+    }
+
+    fn trend(rank: usize, name: &str, promoted: bool) -> SnapshotTrend<'_> {
+        SnapshotTrend {
+            rank,
+            name: name.into(),
+            description: None,
+            domain_context: None,
+            is_ai_trend: false,
+            grouped_trends: vec![],
+            promoted,
+            post_count: None,
+        }
+    }
+
+    #[test]
+    fn merges_snapshots_into_series() {
+        let first = TrendsSnapshot {
+            timestamp: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            location: None,
+            trends: vec![
+                trend(1, "a", false),
+                trend(2, "b", false),
+                trend(3, "ad", true),
+            ],
+        };
+        let second = TrendsSnapshot {
+            timestamp: DateTime::from_timestamp(1_700_003_600, 0).unwrap(),
+            location: None,
+            trends: vec![trend(1, "b", false), trend(4, "a", false)],
+        };
+
+        // Snapshot order should not matter.
+        let series = TrendSeries::from_snapshots([&second, &first]);
+
+        assert_eq!(series.keys().collect::<Vec<_>>(), vec!["a", "b"]);
+
+        let b = &series["b"];
+        assert_eq!(b.first_seen, first.timestamp);
+        assert_eq!(b.last_seen, second.timestamp);
+        assert_eq!(b.peak_rank, 1);
+        assert_eq!(
+            b.observations
+                .iter()
+                .map(|observation| observation.rank)
+                .collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+    }
 }
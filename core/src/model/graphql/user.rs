@@ -1,4 +1,4 @@
-use serde_field_attributes::integer_str;
+use serde_field_attributes::{integer_str, optional_integer_str};
 use std::borrow::Cow;
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
@@ -16,6 +16,15 @@ pub enum UserResult<'a, U> {
     },
 }
 
+/// Unlike most result wrappers, this includes the user ID alongside the result.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct IdentifiedUserResults<U> {
+    #[serde(with = "optional_integer_str", default)]
+    pub rest_id: Option<u64>,
+    pub result: Option<U>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 //#[serde(deny_unknown_fields)]
 pub struct User<'a> {
@@ -0,0 +1,297 @@
+//! Media notes and the tweets they match.
+//!
+//! Match counts come from repeated GraphQL scrapes of the same note, and media keys come from the
+//! `wxj` data (in either format) for the tweet the note was written on.
+use crate::model::{
+    graphql::birdwatch::note::{MediaNoteCategory, Note},
+    wxj::{data::Tweet, flat::TweetSnapshot},
+};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct MatchObservation {
+    pub observed_at: DateTime<Utc>,
+    pub match_count: usize,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MediaNote {
+    pub tweet_id: Option<u64>,
+    pub category: Option<MediaNoteCategory>,
+    /// Ordered by observation time.
+    pub observations: Vec<MatchObservation>,
+    /// The media of the tweet the note was written on.
+    pub media_keys: BTreeSet<String>,
+}
+
+impl MediaNote {
+    #[must_use]
+    pub fn latest_match_count(&self) -> Option<usize> {
+        self.observations
+            .last()
+            .map(|observation| observation.match_count)
+    }
+
+    /// The change in the match count between the first and last observations.
+    #[must_use]
+    pub fn match_count_growth(&self) -> Option<i64> {
+        let first = i64::try_from(self.observations.first()?.match_count).ok()?;
+        let last = i64::try_from(self.observations.last()?.match_count).ok()?;
+
+        Some(last - first)
+    }
+}
+
+/// Media notes keyed by note ID.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MediaNotes {
+    notes: BTreeMap<u64, MediaNote>,
+    /// Note IDs keyed by the ID of the tweet the note was written on.
+    by_tweet_id: HashMap<u64, Vec<u64>>,
+}
+
+impl MediaNotes {
+    /// Record the match count of a scraped note.
+    ///
+    /// Returns `false` if the note is not a media note or has no match count.
+    pub fn add_observation(&mut self, note: &Note<'_>, observed_at: DateTime<Utc>) -> bool {
+        let Some(metadata) = note
+            .metadata()
+            .filter(|metadata| metadata.is_media_note == Some(true))
+        else {
+            return false;
+        };
+
+        // The second version of the count is preferred when both are present.
+        let Some(match_count) = metadata
+            .media_note_matches_v2
+            .map(|matches| matches.match_count)
+            .or(metadata.media_note_matches)
+        else {
+            return false;
+        };
+
+        let media_note = self.notes.entry(metadata.id).or_default();
+
+        if let Some(data) = note.data() {
+            if let (None, Some(tweet_id)) = (media_note.tweet_id, data.tweet_id) {
+                media_note.tweet_id = Some(tweet_id);
+                self.by_tweet_id
+                    .entry(tweet_id)
+                    .or_default()
+                    .push(metadata.id);
+            }
+
+            media_note.category = media_note.category.or(data.media_note_category);
+        }
+
+        let observation = MatchObservation {
+            observed_at,
+            match_count,
+        };
+
+        if let Err(index) = media_note.observations.binary_search(&observation) {
+            media_note.observations.insert(index, observation);
+        }
+
+        true
+    }
+
+    /// Link the media of a tweet to the media notes written on it.
+    ///
+    /// Returns the number of notes linked.
+    pub fn add_tweet(&mut self, tweet: &Tweet<'_>) -> usize {
+        let media_keys = tweet
+            .attachments
+            .as_ref()
+            .and_then(|attachments| attachments.media_keys.as_deref())
+            .unwrap_or_default();
+
+        self.link_media_keys(tweet.id, media_keys)
+    }
+
+    /// Link the media of a tweet in the flat format to the media notes written on it.
+    ///
+    /// Returns the number of notes linked.
+    pub fn add_flat_tweet(&mut self, snapshot: &TweetSnapshot<'_>) -> usize {
+        // The extended entities include every item, while the entities only include the first.
+        let media = snapshot
+            .extended_tweet
+            .as_ref()
+            .and_then(|extended_tweet| extended_tweet.extended_entities.as_ref())
+            .or(snapshot.extended_entities.as_ref())
+            .map(|extended_entities| extended_entities.media.as_slice())
+            .or(snapshot.entities.media.as_deref())
+            .unwrap_or_default();
+        let media_keys = media
+            .iter()
+            .map(crate::model::wxj::flat::media::Media::media_key)
+            .collect::<Vec<_>>();
+
+        self.link_media_keys(snapshot.id, &media_keys)
+    }
+
+    fn link_media_keys(&mut self, tweet_id: u64, media_keys: &[String]) -> usize {
+        match self.by_tweet_id.get(&tweet_id) {
+            Some(note_ids) if !media_keys.is_empty() => {
+                for note_id in note_ids {
+                    if let Some(media_note) = self.notes.get_mut(note_id) {
+                        media_note.media_keys.extend(media_keys.iter().cloned());
+                    }
+                }
+
+                note_ids.len()
+            }
+            _ => 0,
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, note_id: u64) -> Option<&MediaNote> {
+        self.notes.get(&note_id)
+    }
+
+    /// All media notes, ordered by ID.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &MediaNote)> {
+        self.notes
+            .iter()
+            .map(|(note_id, media_note)| (*note_id, media_note))
+    }
+
+    /// Note IDs for each linked media key.
+    #[must_use]
+    pub fn by_media_key(&self) -> BTreeMap<&str, Vec<u64>> {
+        let mut result = BTreeMap::<&str, Vec<u64>>::new();
+
+        for (note_id, media_note) in self.iter() {
+            for media_key in &media_note.media_keys {
+                result.entry(media_key).or_default().push(note_id);
+            }
+        }
+
+        result
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.notes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MediaNotes;
+    use crate::model::{
+        graphql::birdwatch::note::Note,
+        wxj::{data::Tweet, flat::TweetSnapshot},
+    };
+    use chrono::DateTime;
+
+    fn note_json(match_count: usize) -> String {
+        note_json_for(
+            1_900_000_000_000_000_000,
+            1_800_000_000_000_000_000,
+            match_count,
+        )
+    }
+
+    fn note_json_for(note_id: u64, tweet_id: u64, match_count: usize) -> String {
+        format!(
+            concat!(
+                r#"{{"rest_id":"{}","data_v1":{{"classification":"MisinformedOrPotentiallyMisleading","#,
+                r#""summary":{{"text":"This image is edited.","entities":[]}}}},"#,
+                r#""tweet_results":{{"result":{{"rest_id":"{}","media_note_category":"SingleImage"}}}},"#,
+                r#""created_at":1756184544431,"is_media_note":true,"#,
+                r#""media_note_matches_v2":{{"match_count":{},"shoud_show_matches":true}}}}"#
+            ),
+            note_id, tweet_id, match_count
+        )
+    }
+
+    #[test]
+    fn track_media_note_matches() {
+        let t0 = DateTime::from_timestamp(1_756_200_000, 0).unwrap();
+        let t1 = DateTime::from_timestamp(1_756_300_000, 0).unwrap();
+
+        let mut media_notes = MediaNotes::default();
+
+        // Observations can be added out of order.
+        let later = note_json(40);
+        let earlier = note_json(12);
+
+        assert!(
+            media_notes.add_observation(&serde_json::from_str::<Note<'_>>(&later).unwrap(), t1)
+        );
+        assert!(
+            media_notes.add_observation(&serde_json::from_str::<Note<'_>>(&earlier).unwrap(), t0)
+        );
+
+        let tweet = serde_json::from_str::<Tweet<'_>>(concat!(
+            r#"{"id":"1800000000000000000","author_id":"10","conversation_id":"1800000000000000000","#,
+            r#""created_at":"2025-08-01T00:00:00.000Z","lang":"en","possibly_sensitive":false,"#,
+            r#""public_metrics":{"retweet_count":0,"reply_count":0,"like_count":0,"quote_count":0},"#,
+            r#""reply_settings":"everyone","text":"Look at this","#,
+            r#""attachments":{"media_keys":["3_1800000000000000001"]}}"#
+        ))
+        .unwrap();
+
+        assert_eq!(media_notes.add_tweet(&tweet), 1);
+
+        let media_note = media_notes.get(1_900_000_000_000_000_000).unwrap();
+
+        assert_eq!(media_note.latest_match_count(), Some(40));
+        assert_eq!(media_note.match_count_growth(), Some(28));
+        assert_eq!(
+            media_notes.by_media_key().get("3_1800000000000000001"),
+            Some(&vec![1_900_000_000_000_000_000])
+        );
+    }
+
+    #[test]
+    fn link_flat_tweet_media() {
+        // A synthetic snapshot (not a capture), with a photo and an animated GIF.
+        let snapshot = serde_json::from_str::<TweetSnapshot<'_>>(include_str!(
+            "../../../examples/wxj/flat-tweet-synthetic.json"
+        ))
+        .unwrap();
+        let observed_at = DateTime::from_timestamp(1_756_200_000, 0).unwrap();
+
+        let mut media_notes = MediaNotes::default();
+
+        for (note_id, tweet_id) in [
+            (1_900_000_000_000_000_001, snapshot.id),
+            (1_900_000_000_000_000_002, snapshot.id),
+            (1_900_000_000_000_000_003, 1),
+        ] {
+            let json = note_json_for(note_id, tweet_id, 5);
+
+            assert!(media_notes.add_observation(
+                &serde_json::from_str::<Note<'_>>(&json).unwrap(),
+                observed_at
+            ));
+        }
+
+        assert_eq!(media_notes.add_flat_tweet(&snapshot), 2);
+
+        let by_media_key = media_notes.by_media_key();
+
+        assert_eq!(
+            by_media_key.get("3_1580539000000000001"),
+            Some(&vec![1_900_000_000_000_000_001, 1_900_000_000_000_000_002])
+        );
+        assert!(by_media_key.contains_key("16_1580539000000000002"));
+        assert!(
+            media_notes
+                .get(1_900_000_000_000_000_003)
+                .unwrap()
+                .media_keys
+                .is_empty()
+        );
+    }
+}
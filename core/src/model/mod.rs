@@ -5,10 +5,12 @@ pub mod attributes;
 pub mod cashtag;
 pub mod color;
 pub mod country;
+pub mod engagement;
 pub mod entity;
 pub mod graphql;
 pub mod lang;
 pub mod media;
+pub mod media_note;
 pub mod metrics;
 pub mod place;
 pub mod properties;
@@ -16,6 +18,7 @@ pub mod snowflake;
 pub mod source;
 pub mod time_zone;
 pub mod timestamp;
+pub mod tweet;
 pub mod url;
 pub mod user;
 pub mod wxj;
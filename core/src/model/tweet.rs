@@ -0,0 +1,88 @@
+//! A tweet representation that does not depend on the source format (analogous to
+//! [`User`](crate::model::user::User)).
+use crate::model::{
+    lang::Lang,
+    source::{SourceAnchor, SourceName},
+    wxj::{data, flat},
+};
+use bounded_static_derive_more::ToStatic;
+use chrono::{DateTime, Utc};
+use std::borrow::Cow;
+
+#[derive(Clone, Debug, Eq, ToStatic, PartialEq)]
+pub struct Tweet<'a> {
+    pub id: u64,
+    pub author_id: u64,
+    pub created_at: DateTime<Utc>,
+    pub conversation_id: Option<u64>,
+    /// The full text (for long or truncated tweets).
+    pub text: Cow<'a, str>,
+    pub lang: Option<Lang>,
+    pub source: Option<SourceName>,
+    pub retweet_count: Option<usize>,
+    pub reply_count: Option<usize>,
+    pub like_count: Option<usize>,
+    pub quote_count: Option<usize>,
+    pub in_reply_to_tweet_id: Option<u64>,
+    pub in_reply_to_user_id: Option<u64>,
+    pub retweeted_id: Option<u64>,
+    pub quoted_id: Option<u64>,
+}
+
+impl<'a> Tweet<'a> {
+    pub fn from_data(tweet: &data::Tweet<'a>) -> Result<Self, data::FormatError> {
+        Ok(Self {
+            id: tweet.id,
+            author_id: tweet.author_id,
+            created_at: tweet.created_at,
+            conversation_id: Some(tweet.conversation_id),
+            text: tweet
+                .note_tweet
+                .as_ref()
+                .and_then(|note_tweet| note_tweet.text.clone())
+                .unwrap_or_else(|| tweet.text.clone()),
+            lang: Some(tweet.lang),
+            source: tweet.source.clone(),
+            retweet_count: Some(tweet.public_metrics.retweet_count),
+            reply_count: Some(tweet.public_metrics.reply_count),
+            like_count: Some(tweet.public_metrics.like_count),
+            quote_count: Some(tweet.public_metrics.quote_count),
+            in_reply_to_tweet_id: tweet.replied_to_id()?,
+            in_reply_to_user_id: tweet.in_reply_to_user_id,
+            retweeted_id: tweet.retweeted_id()?,
+            quoted_id: tweet.quoted_id()?,
+        })
+    }
+
+    /// Quoted and retweeted tweets are not included.
+    #[must_use]
+    pub fn from_flat(snapshot: &flat::TweetSnapshot<'a>) -> Self {
+        Self {
+            id: snapshot.id,
+            author_id: snapshot.user.id,
+            created_at: snapshot.created_at,
+            conversation_id: None,
+            text: snapshot.extended_tweet.as_ref().map_or_else(
+                || snapshot.text.clone(),
+                |extended_tweet| extended_tweet.full_text.clone(),
+            ),
+            lang: Some(snapshot.lang),
+            source: match &snapshot.source {
+                SourceAnchor::Known(source) => Some(SourceName::Known(*source)),
+                SourceAnchor::Other { name, .. } => Some(SourceName::Other(name.clone())),
+                SourceAnchor::Empty => None,
+            },
+            retweet_count: Some(snapshot.retweet_count),
+            reply_count: snapshot.reply_count,
+            like_count: Some(snapshot.favorite_count),
+            quote_count: snapshot.quote_count,
+            in_reply_to_tweet_id: snapshot.in_reply_to_status_id,
+            in_reply_to_user_id: snapshot.in_reply_to_user_id,
+            retweeted_id: snapshot
+                .retweeted_status
+                .as_ref()
+                .map(|retweeted_status| retweeted_status.id),
+            quoted_id: snapshot.quoted_status_id,
+        }
+    }
+}
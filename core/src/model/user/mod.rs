@@ -37,3 +37,76 @@ pub struct User<'a> {
     pub parody_commentary_fan_label: Option<properties::ParodyCommentaryFanLabel>,
     pub withheld_in_countries: Option<Vec<Country>>,
 }
+
+impl<'a> From<&crate::model::wxj::data::User<'a>> for User<'a> {
+    fn from(value: &crate::model::wxj::data::User<'a>) -> Self {
+        Self {
+            id: value.id,
+            created_at: Some(value.created_at),
+            screen_name: Some(value.username.clone()),
+            name: Some(value.name.clone()),
+            description: Some(value.description.clone()),
+            location: value.location.clone(),
+            url: value.url.clone(),
+            description_urls: None,
+            protected: Some(value.protected),
+            verified: Some(value.verified),
+            blue_verified: None,
+            verified_type: None,
+            followers_count: value.public_metrics.followers_count,
+            friends_count: value.public_metrics.following_count,
+            statuses_count: value.public_metrics.tweet_count,
+            media_count: value.public_metrics.media_count,
+            listed_count: value.public_metrics.listed_count,
+            subscribers_count: None,
+            creator_subscriptions_count: None,
+            pinned_tweet_id: Some(value.pinned_tweet_id),
+            default_profile: None,
+            default_profile_image: None,
+            profile_image_url: Some(value.profile_image_url.clone()),
+            profile_image_shape: None,
+            highlights_info: None,
+            profile_interstitial_type: None,
+            parody_commentary_fan_label: None,
+            withheld_in_countries: value
+                .withheld
+                .as_ref()
+                .map(|withheld| withheld.country_codes.clone()),
+        }
+    }
+}
+
+impl<'a> From<&crate::model::wxj::flat::User<'a>> for User<'a> {
+    fn from(value: &crate::model::wxj::flat::User<'a>) -> Self {
+        Self {
+            id: value.id,
+            created_at: Some(value.created_at),
+            screen_name: Some(value.screen_name.clone()),
+            name: Some(value.name.clone()),
+            description: value.description.clone(),
+            location: value.location.clone(),
+            url: value.url.clone(),
+            description_urls: None,
+            protected: Some(value.protected),
+            verified: Some(value.verified),
+            blue_verified: None,
+            verified_type: None,
+            followers_count: value.followers_count,
+            friends_count: value.friends_count,
+            statuses_count: value.statuses_count,
+            media_count: None,
+            listed_count: value.listed_count,
+            subscribers_count: None,
+            creator_subscriptions_count: None,
+            pinned_tweet_id: None,
+            default_profile: Some(value.default_profile),
+            default_profile_image: Some(value.default_profile_image),
+            profile_image_url: Some(value.profile_image_url_https.clone()),
+            profile_image_shape: None,
+            highlights_info: None,
+            profile_interstitial_type: None,
+            parody_commentary_fan_label: None,
+            withheld_in_countries: value.withheld_in_countries.clone(),
+        }
+    }
+}
@@ -0,0 +1,1329 @@
+//! Conversion of tweet snapshots to Arrow record batches and Parquet files.
+//!
+//! Each snapshot is flattened into six normalized tables (tweets, users, media, entities,
+//! references, and withholdings), which are linked by tweet, user, and media IDs. Tweets and users
+//! also record the ID of the snapshot they were observed in, since counts and profile fields
+//! change over time. Languages, countries, sources, and other enumerations are stored as
+//! dictionary columns.
+use crate::model::{
+    country::Country,
+    lang::Lang,
+    media::MediaType,
+    source::SourceName,
+    wxj::{
+        TweetSnapshot,
+        data::{self, ReferenceType},
+        flat,
+    },
+};
+use arrow::{
+    array::{
+        ArrayBuilder, ArrayRef, BooleanBuilder, StringBuilder, StringDictionaryBuilder,
+        TimestampMillisecondBuilder, UInt64Builder,
+    },
+    datatypes::{DataType, Field, Int32Type, Schema, SchemaRef, TimeUnit},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use chrono::{DateTime, Utc};
+use parquet::{
+    arrow::ArrowWriter,
+    basic::{Compression, ZstdLevel},
+    errors::ParquetError,
+    file::properties::WriterProperties,
+};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+pub const DEFAULT_BATCH_SIZE: usize = 64 * 1024;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("Arrow error")]
+    Arrow(#[from] ArrowError),
+    #[error("Parquet error")]
+    Parquet(#[from] ParquetError),
+}
+
+/// Column builders for one of the normalized tables.
+pub trait Table: Default {
+    /// Used as the file name (without extension) by [`Writer::create`].
+    const NAME: &'static str;
+
+    fn schema() -> SchemaRef;
+    /// The number of rows appended since the last batch was finished.
+    fn len(&self) -> usize;
+    fn finish(&mut self) -> Result<RecordBatch, ArrowError>;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Builders for all of the tables.
+#[derive(Default)]
+pub struct Tables {
+    pub tweets: Tweets,
+    pub users: Users,
+    pub media: Media,
+    pub entities: Entities,
+    pub references: References,
+    pub withholdings: Withholdings,
+}
+
+impl Tables {
+    pub fn add(&mut self, snapshot: &TweetSnapshot<'_>) -> Result<(), ArrowError> {
+        match snapshot {
+            TweetSnapshot::Data(snapshot) => self.add_data(snapshot),
+            TweetSnapshot::Flat(snapshot) => self.add_flat(snapshot),
+        }
+    }
+
+    /// Add the tweet and all included tweets, users, and media.
+    pub fn add_data(&mut self, snapshot: &data::TweetSnapshot<'_>) -> Result<(), ArrowError> {
+        let snapshot_id = snapshot.data.id;
+
+        self.add_data_tweet(snapshot_id, &snapshot.data)?;
+
+        for tweet in snapshot.includes.tweets.iter().flatten() {
+            self.add_data_tweet(snapshot_id, tweet)?;
+        }
+
+        for user in snapshot.includes.users() {
+            self.users.append_data(snapshot_id, user)?;
+
+            for country in user
+                .withheld
+                .iter()
+                .flat_map(|withheld| &withheld.country_codes)
+            {
+                self.withholdings.append(None, Some(user.id), *country)?;
+            }
+        }
+
+        // Media objects do not refer to their tweets, so we link them through the attachments.
+        for media in snapshot.includes.media.iter().flatten() {
+            let media_key = &media.metadata().media_key;
+            let tweet_id = std::iter::once(&snapshot.data)
+                .chain(snapshot.includes.tweets.iter().flatten())
+                .find(|tweet| {
+                    tweet
+                        .attachments
+                        .as_ref()
+                        .and_then(|attachments| attachments.media_keys.as_ref())
+                        .is_some_and(|media_keys| media_keys.iter().any(|key| key == media_key))
+                })
+                .map(|tweet| tweet.id);
+
+            self.media.append_data(tweet_id, media)?;
+        }
+
+        Ok(())
+    }
+
+    fn add_data_tweet(
+        &mut self,
+        snapshot_id: u64,
+        tweet: &data::Tweet<'_>,
+    ) -> Result<(), ArrowError> {
+        self.tweets.append_data(snapshot_id, tweet)?;
+
+        // Long tweets have their full text and entities in the note tweet.
+        let entities = tweet
+            .note_tweet
+            .as_ref()
+            .and_then(|note_tweet| note_tweet.entities.as_ref())
+            .or(tweet.entities.as_ref());
+
+        if let Some(entities) = entities {
+            self.entities.append_data(tweet.id, entities)?;
+        }
+
+        for referenced_tweet in tweet.referenced_tweets.iter().flatten() {
+            self.references.append(
+                tweet.id,
+                referenced_tweet.id,
+                referenced_tweet.reference_type,
+            )?;
+        }
+
+        for country in tweet
+            .withheld
+            .iter()
+            .flat_map(|withheld| &withheld.country_codes)
+        {
+            self.withholdings.append(Some(tweet.id), None, *country)?;
+        }
+
+        Ok(())
+    }
+
+    /// Add the tweet and any quoted or retweeted tweets (recursively).
+    pub fn add_flat(&mut self, snapshot: &flat::TweetSnapshot<'_>) -> Result<(), ArrowError> {
+        self.add_flat_tweet(snapshot.id, snapshot)
+    }
+
+    fn add_flat_tweet(
+        &mut self,
+        snapshot_id: u64,
+        tweet: &flat::TweetSnapshot<'_>,
+    ) -> Result<(), ArrowError> {
+        self.tweets.append_flat(snapshot_id, tweet)?;
+        self.users.append_flat(snapshot_id, &tweet.user)?;
+
+        // Truncated tweets have their full text and entities in the extended tweet.
+        let (entities, extended_entities) = tweet.extended_tweet.as_ref().map_or(
+            (&tweet.entities, tweet.extended_entities.as_ref()),
+            |extended_tweet| {
+                (
+                    &extended_tweet.entities,
+                    extended_tweet.extended_entities.as_ref(),
+                )
+            },
+        );
+
+        self.entities.append_flat(tweet.id, entities)?;
+
+        // The extended entities include all media, while the entities only include the first.
+        let media = extended_entities.map_or_else(
+            || entities.media.as_deref().unwrap_or_default(),
+            |extended_entities| &extended_entities.media,
+        );
+
+        for media in media {
+            self.media.append_flat(tweet.id, media)?;
+        }
+
+        // A retweet of a quote tweet also carries the quoted tweet, which belongs to the retweeted
+        // tweet (and is added with it).
+        let quoted_status = tweet
+            .quoted_status
+            .as_deref()
+            .filter(|_| tweet.retweeted_status.is_none());
+
+        let references = [
+            (tweet.in_reply_to_status_id, ReferenceType::RepliedTo),
+            (
+                tweet
+                    .quoted_status_id
+                    .or_else(|| tweet.quoted_status.as_ref().map(|quoted| quoted.id))
+                    .filter(|_| tweet.retweeted_status.is_none()),
+                ReferenceType::Quoted,
+            ),
+            (
+                tweet
+                    .retweeted_status
+                    .as_ref()
+                    .map(|retweeted| retweeted.id),
+                ReferenceType::Retweeted,
+            ),
+        ];
+
+        for (referenced_id, reference_type) in references {
+            if let Some(referenced_id) = referenced_id {
+                self.references
+                    .append(tweet.id, referenced_id, reference_type)?;
+            }
+        }
+
+        for country in tweet.withheld_in_countries.iter().flatten() {
+            self.withholdings.append(Some(tweet.id), None, *country)?;
+        }
+
+        for country in tweet.user.withheld_in_countries.iter().flatten() {
+            self.withholdings
+                .append(None, Some(tweet.user.id), *country)?;
+        }
+
+        if let Some(quoted_status) = quoted_status {
+            self.add_flat_tweet(snapshot_id, quoted_status)?;
+        }
+
+        if let Some(retweeted_status) = &tweet.retweeted_status {
+            self.add_flat_tweet(snapshot_id, retweeted_status)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes snapshots to one Parquet file per table in batches.
+pub struct Writer<W: Write + Send> {
+    tables: Tables,
+    tweets: ArrowWriter<W>,
+    users: ArrowWriter<W>,
+    media: ArrowWriter<W>,
+    entities: ArrowWriter<W>,
+    references: ArrowWriter<W>,
+    withholdings: ArrowWriter<W>,
+    batch_size: usize,
+    snapshot_count: usize,
+}
+
+impl Writer<File> {
+    /// Create a file for each table in the given directory (for example `tweets.parquet`).
+    pub fn create<P: AsRef<Path>>(dir: P, batch_size: usize) -> Result<Self, Error> {
+        Self::new(
+            |name| File::create(dir.as_ref().join(format!("{name}.parquet"))),
+            batch_size,
+        )
+    }
+}
+
+impl<W: Write + Send> Writer<W> {
+    /// The `open` function is called once for each table name.
+    pub fn new<F: FnMut(&str) -> std::io::Result<W>>(
+        mut open: F,
+        batch_size: usize,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            tables: Tables::default(),
+            tweets: Self::table_writer::<Tweets, _>(&mut open, batch_size)?,
+            users: Self::table_writer::<Users, _>(&mut open, batch_size)?,
+            media: Self::table_writer::<Media, _>(&mut open, batch_size)?,
+            entities: Self::table_writer::<Entities, _>(&mut open, batch_size)?,
+            references: Self::table_writer::<References, _>(&mut open, batch_size)?,
+            withholdings: Self::table_writer::<Withholdings, _>(&mut open, batch_size)?,
+            batch_size,
+            snapshot_count: 0,
+        })
+    }
+
+    fn table_writer<T: Table, F: FnMut(&str) -> std::io::Result<W>>(
+        open: &mut F,
+        batch_size: usize,
+    ) -> Result<ArrowWriter<W>, Error> {
+        let properties = WriterProperties::builder()
+            .set_max_row_group_size(batch_size)
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .build();
+
+        Ok(ArrowWriter::try_new(
+            open(T::NAME)?,
+            T::schema(),
+            Some(properties),
+        )?)
+    }
+
+    pub fn write(&mut self, snapshot: &TweetSnapshot<'_>) -> Result<(), Error> {
+        self.tables.add(snapshot)?;
+        self.snapshot_count += 1;
+        self.flush(self.batch_size)
+    }
+
+    pub fn write_data(&mut self, snapshot: &data::TweetSnapshot<'_>) -> Result<(), Error> {
+        self.tables.add_data(snapshot)?;
+        self.snapshot_count += 1;
+        self.flush(self.batch_size)
+    }
+
+    pub fn write_flat(&mut self, snapshot: &flat::TweetSnapshot<'_>) -> Result<(), Error> {
+        self.tables.add_flat(snapshot)?;
+        self.snapshot_count += 1;
+        self.flush(self.batch_size)
+    }
+
+    /// Write every table with at least `min_len` buffered rows.
+    fn flush(&mut self, min_len: usize) -> Result<(), Error> {
+        flush_table(&mut self.tables.tweets, &mut self.tweets, min_len)?;
+        flush_table(&mut self.tables.users, &mut self.users, min_len)?;
+        flush_table(&mut self.tables.media, &mut self.media, min_len)?;
+        flush_table(&mut self.tables.entities, &mut self.entities, min_len)?;
+        flush_table(&mut self.tables.references, &mut self.references, min_len)?;
+        flush_table(
+            &mut self.tables.withholdings,
+            &mut self.withholdings,
+            min_len,
+        )
+    }
+
+    /// Write any buffered rows and the file footers, returning the number of snapshots written.
+    pub fn close(mut self) -> Result<usize, Error> {
+        self.flush(0)?;
+
+        for writer in [
+            self.tweets,
+            self.users,
+            self.media,
+            self.entities,
+            self.references,
+            self.withholdings,
+        ] {
+            writer.close()?;
+        }
+
+        Ok(self.snapshot_count)
+    }
+}
+
+fn flush_table<T: Table, W: Write + Send>(
+    table: &mut T,
+    writer: &mut ArrowWriter<W>,
+    min_len: usize,
+) -> Result<(), Error> {
+    if !table.is_empty() && table.len() >= min_len {
+        writer.write(&table.finish()?)?;
+    }
+
+    Ok(())
+}
+
+fn timestamp_type() -> DataType {
+    DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+}
+
+// Sources are open-ended, so we use wider keys than the public data tables.
+fn dictionary_type() -> DataType {
+    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+}
+
+const fn count(value: usize) -> u64 {
+    value as u64
+}
+
+struct TimestampColumn(TimestampMillisecondBuilder);
+
+impl Default for TimestampColumn {
+    fn default() -> Self {
+        Self(TimestampMillisecondBuilder::new().with_timezone("UTC"))
+    }
+}
+
+impl TimestampColumn {
+    fn append(&mut self, value: DateTime<Utc>) {
+        self.0.append_value(value.timestamp_millis());
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        Arc::new(self.0.finish())
+    }
+}
+
+#[derive(Default)]
+struct DictionaryColumn(StringDictionaryBuilder<Int32Type>);
+
+impl DictionaryColumn {
+    fn append(&mut self, value: Option<&str>) -> Result<(), ArrowError> {
+        if let Some(value) = value {
+            self.0.append(value).map(|_| ())
+        } else {
+            self.0.append_null();
+
+            Ok(())
+        }
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        Arc::new(self.0.finish())
+    }
+}
+
+#[derive(Default)]
+pub struct Tweets {
+    tweet_id: UInt64Builder,
+    snapshot_id: UInt64Builder,
+    author_id: UInt64Builder,
+    created_at: TimestampColumn,
+    conversation_id: UInt64Builder,
+    lang: DictionaryColumn,
+    source: DictionaryColumn,
+    text: StringBuilder,
+    in_reply_to_user_id: UInt64Builder,
+    retweet_count: UInt64Builder,
+    reply_count: UInt64Builder,
+    like_count: UInt64Builder,
+    quote_count: UInt64Builder,
+    possibly_sensitive: BooleanBuilder,
+}
+
+impl Tweets {
+    fn append_data(&mut self, snapshot_id: u64, tweet: &data::Tweet<'_>) -> Result<(), ArrowError> {
+        let text = tweet
+            .note_tweet
+            .as_ref()
+            .and_then(|note_tweet| note_tweet.text.as_deref())
+            .unwrap_or(&tweet.text);
+
+        self.tweet_id.append_value(tweet.id);
+        self.snapshot_id.append_value(snapshot_id);
+        self.author_id.append_value(tweet.author_id);
+        self.created_at.append(tweet.created_at);
+        self.conversation_id.append_value(tweet.conversation_id);
+        self.lang.append(Some(tweet.lang.as_str()))?;
+        self.source
+            .append(tweet.source.as_ref().map(SourceName::name))?;
+        self.text.append_value(text);
+        self.in_reply_to_user_id
+            .append_option(tweet.in_reply_to_user_id);
+        self.retweet_count
+            .append_value(count(tweet.public_metrics.retweet_count));
+        self.reply_count
+            .append_value(count(tweet.public_metrics.reply_count));
+        self.like_count
+            .append_value(count(tweet.public_metrics.like_count));
+        self.quote_count
+            .append_value(count(tweet.public_metrics.quote_count));
+        self.possibly_sensitive
+            .append_value(tweet.possibly_sensitive);
+
+        Ok(())
+    }
+
+    fn append_flat(
+        &mut self,
+        snapshot_id: u64,
+        tweet: &flat::TweetSnapshot<'_>,
+    ) -> Result<(), ArrowError> {
+        let text = tweet
+            .extended_tweet
+            .as_ref()
+            .map_or(&tweet.text, |extended_tweet| &extended_tweet.full_text);
+
+        self.tweet_id.append_value(tweet.id);
+        self.snapshot_id.append_value(snapshot_id);
+        self.author_id.append_value(tweet.user.id);
+        self.created_at.append(tweet.created_at);
+        self.conversation_id.append_null();
+        self.lang.append(Some(tweet.lang.as_str()))?;
+        self.source.append(tweet.source.name())?;
+        self.text.append_value(text);
+        self.in_reply_to_user_id
+            .append_option(tweet.in_reply_to_user_id);
+        self.retweet_count.append_value(count(tweet.retweet_count));
+        self.reply_count.append_option(tweet.reply_count.map(count));
+        self.like_count.append_value(count(tweet.favorite_count));
+        self.quote_count.append_option(tweet.quote_count.map(count));
+        self.possibly_sensitive
+            .append_option(tweet.possibly_sensitive);
+
+        Ok(())
+    }
+}
+
+impl Table for Tweets {
+    const NAME: &'static str = "tweets";
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("tweet_id", DataType::UInt64, false),
+            Field::new("snapshot_id", DataType::UInt64, false),
+            Field::new("author_id", DataType::UInt64, false),
+            Field::new("created_at", timestamp_type(), false),
+            Field::new("conversation_id", DataType::UInt64, true),
+            Field::new("lang", dictionary_type(), false),
+            Field::new("source", dictionary_type(), true),
+            Field::new("text", DataType::Utf8, false),
+            Field::new("in_reply_to_user_id", DataType::UInt64, true),
+            Field::new("retweet_count", DataType::UInt64, false),
+            Field::new("reply_count", DataType::UInt64, true),
+            Field::new("like_count", DataType::UInt64, false),
+            Field::new("quote_count", DataType::UInt64, true),
+            Field::new("possibly_sensitive", DataType::Boolean, true),
+        ]))
+    }
+
+    fn len(&self) -> usize {
+        self.tweet_id.len()
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch, ArrowError> {
+        RecordBatch::try_new(
+            Self::schema(),
+            vec![
+                Arc::new(self.tweet_id.finish()),
+                Arc::new(self.snapshot_id.finish()),
+                Arc::new(self.author_id.finish()),
+                self.created_at.finish(),
+                Arc::new(self.conversation_id.finish()),
+                self.lang.finish(),
+                self.source.finish(),
+                Arc::new(self.text.finish()),
+                Arc::new(self.in_reply_to_user_id.finish()),
+                Arc::new(self.retweet_count.finish()),
+                Arc::new(self.reply_count.finish()),
+                Arc::new(self.like_count.finish()),
+                Arc::new(self.quote_count.finish()),
+                Arc::new(self.possibly_sensitive.finish()),
+            ],
+        )
+    }
+}
+
+#[derive(Default)]
+pub struct Users {
+    user_id: UInt64Builder,
+    snapshot_id: UInt64Builder,
+    screen_name: StringBuilder,
+    name: StringBuilder,
+    created_at: TimestampColumn,
+    description: StringBuilder,
+    location: StringBuilder,
+    url: StringBuilder,
+    protected: BooleanBuilder,
+    verified: BooleanBuilder,
+    followers_count: UInt64Builder,
+    following_count: UInt64Builder,
+    tweet_count: UInt64Builder,
+    listed_count: UInt64Builder,
+    lang: DictionaryColumn,
+}
+
+impl Users {
+    fn append_data(&mut self, snapshot_id: u64, user: &data::User<'_>) -> Result<(), ArrowError> {
+        self.user_id.append_value(user.id);
+        self.snapshot_id.append_value(snapshot_id);
+        self.screen_name.append_value(&user.username);
+        self.name.append_value(&user.name);
+        self.created_at.append(user.created_at);
+        self.description.append_value(&user.description);
+        self.location.append_option(user.location.as_deref());
+        self.url.append_option(user.url.as_deref());
+        self.protected.append_value(user.protected);
+        self.verified.append_value(user.verified);
+        self.followers_count
+            .append_option(user.public_metrics.followers_count.map(count));
+        self.following_count
+            .append_option(user.public_metrics.following_count.map(count));
+        self.tweet_count
+            .append_option(user.public_metrics.tweet_count.map(count));
+        self.listed_count
+            .append_option(user.public_metrics.listed_count.map(count));
+        self.lang.append(None)
+    }
+
+    fn append_flat(&mut self, snapshot_id: u64, user: &flat::User<'_>) -> Result<(), ArrowError> {
+        self.user_id.append_value(user.id);
+        self.snapshot_id.append_value(snapshot_id);
+        self.screen_name.append_value(&user.screen_name);
+        self.name.append_value(&user.name);
+        self.created_at.append(user.created_at);
+        self.description.append_option(user.description.as_deref());
+        self.location.append_option(user.location.as_deref());
+        self.url.append_option(user.url.as_deref());
+        self.protected.append_value(user.protected);
+        self.verified.append_value(user.verified);
+        self.followers_count
+            .append_option(user.followers_count.map(count));
+        self.following_count
+            .append_option(user.friends_count.map(count));
+        self.tweet_count
+            .append_option(user.statuses_count.map(count));
+        self.listed_count
+            .append_option(user.listed_count.map(count));
+        self.lang.append(user.lang.as_ref().map(Lang::as_str))
+    }
+}
+
+impl Table for Users {
+    const NAME: &'static str = "users";
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("user_id", DataType::UInt64, false),
+            Field::new("snapshot_id", DataType::UInt64, false),
+            Field::new("screen_name", DataType::Utf8, false),
+            Field::new("name", DataType::Utf8, false),
+            Field::new("created_at", timestamp_type(), false),
+            Field::new("description", DataType::Utf8, true),
+            Field::new("location", DataType::Utf8, true),
+            Field::new("url", DataType::Utf8, true),
+            Field::new("protected", DataType::Boolean, false),
+            Field::new("verified", DataType::Boolean, false),
+            Field::new("followers_count", DataType::UInt64, true),
+            Field::new("following_count", DataType::UInt64, true),
+            Field::new("tweet_count", DataType::UInt64, true),
+            Field::new("listed_count", DataType::UInt64, true),
+            Field::new("lang", dictionary_type(), true),
+        ]))
+    }
+
+    fn len(&self) -> usize {
+        self.user_id.len()
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch, ArrowError> {
+        RecordBatch::try_new(
+            Self::schema(),
+            vec![
+                Arc::new(self.user_id.finish()),
+                Arc::new(self.snapshot_id.finish()),
+                Arc::new(self.screen_name.finish()),
+                Arc::new(self.name.finish()),
+                self.created_at.finish(),
+                Arc::new(self.description.finish()),
+                Arc::new(self.location.finish()),
+                Arc::new(self.url.finish()),
+                Arc::new(self.protected.finish()),
+                Arc::new(self.verified.finish()),
+                Arc::new(self.followers_count.finish()),
+                Arc::new(self.following_count.finish()),
+                Arc::new(self.tweet_count.finish()),
+                Arc::new(self.listed_count.finish()),
+                self.lang.finish(),
+            ],
+        )
+    }
+}
+
+const fn media_type_name(media_type: MediaType) -> &'static str {
+    match media_type {
+        MediaType::Photo => "photo",
+        MediaType::Video => "video",
+        MediaType::AnimatedGif => "animated_gif",
+    }
+}
+
+// Column names match the API fields (`media_key` etc.).
+#[allow(clippy::struct_field_names)]
+#[derive(Default)]
+pub struct Media {
+    media_id: UInt64Builder,
+    media_key: StringBuilder,
+    tweet_id: UInt64Builder,
+    media_type: DictionaryColumn,
+    url: StringBuilder,
+    width: UInt64Builder,
+    height: UInt64Builder,
+}
+
+impl Media {
+    fn append_data(
+        &mut self,
+        tweet_id: Option<u64>,
+        media: &data::media::Media<'_>,
+    ) -> Result<(), ArrowError> {
+        let metadata = media.metadata();
+        // Media keys have the form `3_1800000000000000001`, with the media ID after the prefix.
+        let media_id = metadata
+            .media_key
+            .split_once('_')
+            .and_then(|(_, media_id)| media_id.parse::<u64>().ok());
+
+        self.media_id.append_option(media_id);
+        self.media_key.append_value(&metadata.media_key);
+        self.tweet_id.append_option(tweet_id);
+        self.media_type
+            .append(Some(media_type_name(media.media_type())))?;
+        self.url.append_option(media.url());
+        self.width.append_value(count(metadata.width));
+        self.height.append_value(count(metadata.height));
+
+        Ok(())
+    }
+
+    fn append_flat(
+        &mut self,
+        tweet_id: u64,
+        media: &flat::media::Media<'_>,
+    ) -> Result<(), ArrowError> {
+        self.media_id.append_value(media.id);
+        self.media_key.append_value(media.media_key());
+        self.tweet_id.append_value(tweet_id);
+        self.media_type
+            .append(Some(media_type_name(media.media_type)))?;
+        self.url.append_value(&media.media_url);
+        self.width.append_value(count(media.sizes.large.w));
+        self.height.append_value(count(media.sizes.large.h));
+
+        Ok(())
+    }
+}
+
+impl Table for Media {
+    const NAME: &'static str = "media";
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("media_id", DataType::UInt64, true),
+            Field::new("media_key", DataType::Utf8, true),
+            Field::new("tweet_id", DataType::UInt64, true),
+            Field::new("media_type", dictionary_type(), false),
+            Field::new("url", DataType::Utf8, true),
+            Field::new("width", DataType::UInt64, false),
+            Field::new("height", DataType::UInt64, false),
+        ]))
+    }
+
+    fn len(&self) -> usize {
+        self.media_id.len()
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch, ArrowError> {
+        RecordBatch::try_new(
+            Self::schema(),
+            vec![
+                Arc::new(self.media_id.finish()),
+                Arc::new(self.media_key.finish()),
+                Arc::new(self.tweet_id.finish()),
+                self.media_type.finish(),
+                Arc::new(self.url.finish()),
+                Arc::new(self.width.finish()),
+                Arc::new(self.height.finish()),
+            ],
+        )
+    }
+}
+
+const HASHTAG: &str = "hashtag";
+const URL: &str = "url";
+const MENTION: &str = "mention";
+const CASHTAG: &str = "cashtag";
+
+/// Hashtags, URLs, mentions, and cashtags, with their character offsets in the tweet text.
+#[derive(Default)]
+pub struct Entities {
+    tweet_id: UInt64Builder,
+    entity_type: DictionaryColumn,
+    start: UInt64Builder,
+    end: UInt64Builder,
+    text: StringBuilder,
+    user_id: UInt64Builder,
+}
+
+impl Entities {
+    fn append(
+        &mut self,
+        tweet_id: u64,
+        entity_type: &str,
+        start: usize,
+        end: usize,
+        text: &str,
+        user_id: Option<u64>,
+    ) -> Result<(), ArrowError> {
+        self.tweet_id.append_value(tweet_id);
+        self.entity_type.append(Some(entity_type))?;
+        self.start.append_value(count(start));
+        self.end.append_value(count(end));
+        self.text.append_value(text);
+        self.user_id.append_option(user_id);
+
+        Ok(())
+    }
+
+    fn append_data(
+        &mut self,
+        tweet_id: u64,
+        entities: &data::entity::TweetEntities<'_>,
+    ) -> Result<(), ArrowError> {
+        for hashtag in entities.hashtags.iter().flatten() {
+            self.append(
+                tweet_id,
+                HASHTAG,
+                hashtag.start,
+                hashtag.end,
+                &hashtag.tag,
+                None,
+            )?;
+        }
+
+        for url in entities.urls.iter().flatten() {
+            self.append(
+                tweet_id,
+                URL,
+                url.start,
+                url.end,
+                url.expanded_url.as_deref().unwrap_or(&url.url),
+                None,
+            )?;
+        }
+
+        for mention in entities.mentions.iter().flatten() {
+            self.append(
+                tweet_id,
+                MENTION,
+                mention.start,
+                mention.end,
+                &mention.username,
+                mention.id,
+            )?;
+        }
+
+        for cashtag in entities.cashtags.iter().flatten() {
+            self.append(
+                tweet_id,
+                CASHTAG,
+                cashtag.start,
+                cashtag.end,
+                &cashtag.tag.to_string(),
+                None,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn append_flat(
+        &mut self,
+        tweet_id: u64,
+        entities: &flat::entity::TweetEntities<'_>,
+    ) -> Result<(), ArrowError> {
+        for hashtag in &entities.hashtags {
+            self.append(
+                tweet_id,
+                HASHTAG,
+                hashtag.indices.start,
+                hashtag.indices.end,
+                &hashtag.text,
+                None,
+            )?;
+        }
+
+        for url in &entities.urls {
+            self.append(
+                tweet_id,
+                URL,
+                url.indices.start,
+                url.indices.end,
+                url.expanded_url.as_deref().unwrap_or(&url.url),
+                None,
+            )?;
+        }
+
+        for mention in &entities.user_mentions {
+            self.append(
+                tweet_id,
+                MENTION,
+                mention.indices.start,
+                mention.indices.end,
+                &mention.screen_name,
+                mention.id,
+            )?;
+        }
+
+        for symbol in &entities.symbols {
+            self.append(
+                tweet_id,
+                CASHTAG,
+                symbol.indices.start,
+                symbol.indices.end,
+                &symbol.text.to_string(),
+                None,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Table for Entities {
+    const NAME: &'static str = "entities";
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("tweet_id", DataType::UInt64, false),
+            Field::new("entity_type", dictionary_type(), false),
+            Field::new("start", DataType::UInt64, false),
+            Field::new("end", DataType::UInt64, false),
+            Field::new("text", DataType::Utf8, false),
+            Field::new("user_id", DataType::UInt64, true),
+        ]))
+    }
+
+    fn len(&self) -> usize {
+        self.tweet_id.len()
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch, ArrowError> {
+        RecordBatch::try_new(
+            Self::schema(),
+            vec![
+                Arc::new(self.tweet_id.finish()),
+                self.entity_type.finish(),
+                Arc::new(self.start.finish()),
+                Arc::new(self.end.finish()),
+                Arc::new(self.text.finish()),
+                Arc::new(self.user_id.finish()),
+            ],
+        )
+    }
+}
+
+const fn reference_type_name(reference_type: ReferenceType) -> &'static str {
+    match reference_type {
+        ReferenceType::Retweeted => "retweeted",
+        ReferenceType::RepliedTo => "replied_to",
+        ReferenceType::Quoted => "quoted",
+    }
+}
+
+#[derive(Default)]
+pub struct References {
+    tweet_id: UInt64Builder,
+    referenced_tweet_id: UInt64Builder,
+    reference_type: DictionaryColumn,
+}
+
+impl References {
+    fn append(
+        &mut self,
+        tweet_id: u64,
+        referenced_tweet_id: u64,
+        reference_type: ReferenceType,
+    ) -> Result<(), ArrowError> {
+        self.tweet_id.append_value(tweet_id);
+        self.referenced_tweet_id.append_value(referenced_tweet_id);
+        self.reference_type
+            .append(Some(reference_type_name(reference_type)))
+    }
+}
+
+impl Table for References {
+    const NAME: &'static str = "references";
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("tweet_id", DataType::UInt64, false),
+            Field::new("referenced_tweet_id", DataType::UInt64, false),
+            Field::new("reference_type", dictionary_type(), false),
+        ]))
+    }
+
+    fn len(&self) -> usize {
+        self.tweet_id.len()
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch, ArrowError> {
+        RecordBatch::try_new(
+            Self::schema(),
+            vec![
+                Arc::new(self.tweet_id.finish()),
+                Arc::new(self.referenced_tweet_id.finish()),
+                self.reference_type.finish(),
+            ],
+        )
+    }
+}
+
+/// Countries in which a tweet or user is withheld (exactly one of the IDs is set).
+#[derive(Default)]
+pub struct Withholdings {
+    tweet_id: UInt64Builder,
+    user_id: UInt64Builder,
+    country: DictionaryColumn,
+}
+
+impl Withholdings {
+    fn append(
+        &mut self,
+        tweet_id: Option<u64>,
+        user_id: Option<u64>,
+        country: Country,
+    ) -> Result<(), ArrowError> {
+        self.tweet_id.append_option(tweet_id);
+        self.user_id.append_option(user_id);
+        self.country.append(Some(&country.to_string()))
+    }
+}
+
+impl Table for Withholdings {
+    const NAME: &'static str = "withholdings";
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("tweet_id", DataType::UInt64, true),
+            Field::new("user_id", DataType::UInt64, true),
+            Field::new("country", dictionary_type(), false),
+        ]))
+    }
+
+    fn len(&self) -> usize {
+        self.country.0.len()
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch, ArrowError> {
+        RecordBatch::try_new(
+            Self::schema(),
+            vec![
+                Arc::new(self.tweet_id.finish()),
+                Arc::new(self.user_id.finish()),
+                self.country.finish(),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Media, References, Table, Tables, Tweets, Users, Withholdings};
+    use crate::model::wxj::{data::TweetSnapshot, flat};
+    use arrow::{
+        array::{Array, AsArray},
+        datatypes::{Int32Type, UInt64Type},
+        record_batch::RecordBatch,
+    };
+    use parquet::{
+        arrow::arrow_reader::ParquetRecordBatchReaderBuilder,
+        basic::{Compression, ZstdLevel},
+    };
+
+    const USER: &str = concat!(
+        r#"{"id":"10","username":"alice","name":"Alice","created_at":"2010-01-01T00:00:00.000Z","#,
+        r#""description":"","profile_image_url":"https://example.com/a.jpg","verified":false,"#,
+        r#""protected":false,"public_metrics":{"followers_count":5,"following_count":1,"#,
+        r#""tweet_count":2,"listed_count":0},"withheld":{"country_codes":["DE","FR"]}}"#
+    );
+
+    fn tweet(id: u64, extra: &str) -> String {
+        format!(
+            concat!(
+                r#"{{"id":"{id}","author_id":"10","conversation_id":"{id}","#,
+                r#""created_at":"2025-08-01T00:00:00.000Z","lang":"en","possibly_sensitive":false,"#,
+                r#""public_metrics":{{"retweet_count":0,"reply_count":0,"like_count":3,"quote_count":0}},"#,
+                r#""reply_settings":"everyone","text":"Hello #world","source":"Twitter Web App"{extra}}}"#
+            ),
+            id = id,
+            extra = extra
+        )
+    }
+
+    fn snapshot() -> String {
+        let data = tweet(
+            2,
+            concat!(
+                r#","referenced_tweets":[{"type":"quoted","id":"1"}],"#,
+                r#""entities":{"hashtags":[{"start":6,"end":12,"tag":"world"}]}"#
+            ),
+        );
+        let quoted = tweet(
+            1,
+            r#","attachments":{"media_keys":["3_1800000000000000001"]}"#,
+        );
+
+        format!(
+            concat!(
+                r#"{{"data":{},"includes":{{"users":[{}],"tweets":[{}],"media":[{{"type":"photo","#,
+                r#""media_key":"3_1800000000000000001","height":100,"width":200,"#,
+                r#""url":"https://example.com/m.jpg"}}]}}}}"#
+            ),
+            data, USER, quoted
+        )
+    }
+
+    #[test]
+    fn write_snapshot_tables() {
+        let json = snapshot();
+        let snapshot = serde_json::from_str::<TweetSnapshot<'_>>(&json).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut writer = super::Writer::create(dir.path(), super::DEFAULT_BATCH_SIZE).unwrap();
+        writer.write_data(&snapshot).unwrap();
+        assert_eq!(writer.close().unwrap(), 1);
+
+        let read = |name: &str| {
+            let file = std::fs::File::open(dir.path().join(format!("{name}.parquet"))).unwrap();
+
+            ParquetRecordBatchReaderBuilder::try_new(file)
+                .unwrap()
+                .build()
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
+
+        let tweets = read(Tweets::NAME);
+        let metadata = ParquetRecordBatchReaderBuilder::try_new(
+            std::fs::File::open(dir.path().join(format!("{}.parquet", Tweets::NAME))).unwrap(),
+        )
+        .unwrap()
+        .metadata()
+        .clone();
+
+        assert_eq!(
+            metadata.row_group(0).column(0).compression(),
+            Compression::ZSTD(ZstdLevel::default())
+        );
+
+        // The quoted tweet is included as a separate row, observed in the same snapshot.
+        assert_eq!(tweets.iter().map(RecordBatch::num_rows).sum::<usize>(), 2);
+        assert_eq!(tweets[0].schema(), Tweets::schema());
+
+        let lang = tweets[0]
+            .column_by_name("lang")
+            .unwrap()
+            .as_dictionary::<Int32Type>();
+
+        assert_eq!(lang.values().as_string::<i32>().value(0), "en");
+
+        let source = tweets[0]
+            .column_by_name("source")
+            .unwrap()
+            .as_dictionary::<Int32Type>();
+
+        assert_eq!(
+            source.values().as_string::<i32>().value(0),
+            "Twitter Web App"
+        );
+
+        assert_eq!(read(Users::NAME)[0].num_rows(), 1);
+        assert_eq!(read(References::NAME)[0].num_rows(), 1);
+        assert_eq!(read(super::Entities::NAME)[0].num_rows(), 1);
+
+        let media = read(Media::NAME);
+
+        assert_eq!(
+            media[0]
+                .column_by_name("media_id")
+                .unwrap()
+                .as_primitive::<UInt64Type>()
+                .value(0),
+            1_800_000_000_000_000_001
+        );
+        assert_eq!(
+            media[0]
+                .column_by_name("tweet_id")
+                .unwrap()
+                .as_primitive::<UInt64Type>()
+                .value(0),
+            1
+        );
+
+        let withholdings = read(Withholdings::NAME);
+        let countries = withholdings[0]
+            .column_by_name("country")
+            .unwrap()
+            .as_dictionary::<Int32Type>();
+
+        assert_eq!(countries.len(), 2);
+        assert_eq!(withholdings[0].column(0).null_count(), 2);
+    }
+
+    /// A retweet (4) of a truncated tweet (2) that quotes another tweet (3), built from the
+    /// synthetic flat snapshot. As in the flat format, the retweet also carries the quoted tweet.
+    fn flat_snapshot() -> serde_json::Value {
+        let base = serde_json::from_str::<serde_json::Value>(include_str!(
+            "../../../../examples/wxj/flat-tweet-synthetic.json"
+        ))
+        .unwrap();
+
+        let tweet = |id: u64| {
+            let mut tweet = base.clone();
+            tweet["id"] = id.into();
+            tweet["id_str"] = id.to_string().into();
+            tweet["in_reply_to_status_id"] = serde_json::Value::Null;
+            tweet["in_reply_to_status_id_str"] = serde_json::Value::Null;
+            tweet
+        };
+
+        // Without extended entities, so the media come from the entities.
+        let mut quoted = tweet(3);
+        quoted.as_object_mut().unwrap().remove("extended_entities");
+
+        let mut retweeted = base.clone();
+        retweeted["id"] = 2.into();
+        retweeted["id_str"] = "2".into();
+        retweeted["truncated"] = true.into();
+        retweeted["is_quote_status"] = true.into();
+        retweeted["quoted_status_id"] = 3.into();
+        retweeted["quoted_status_id_str"] = "3".into();
+        retweeted["quoted_status"] = quoted.clone();
+        retweeted["extended_tweet"] = serde_json::json!({
+            "full_text": "@bob Look at this #long text https://t.co/AbCdEfGhIj",
+            "display_text_range": [0, 28],
+            "entities": {
+                "hashtags": [{"text": "long", "indices": [18, 23]}],
+                "urls": [],
+                "user_mentions": [],
+                "symbols": []
+            },
+            "extended_entities": base["extended_entities"].clone()
+        });
+        retweeted
+            .as_object_mut()
+            .unwrap()
+            .remove("extended_entities");
+
+        let mut retweet = tweet(4);
+        retweet["quoted_status_id"] = 3.into();
+        retweet["quoted_status_id_str"] = "3".into();
+        retweet["quoted_status"] = quoted;
+        retweet["retweeted_status"] = retweeted;
+        retweet["entities"]["media"] = serde_json::Value::Null;
+        retweet["user"]["withheld_in_countries"] = serde_json::json!(["DE", "FR"]);
+        retweet.as_object_mut().unwrap().remove("extended_entities");
+
+        retweet
+    }
+
+    #[test]
+    fn add_flat_tables() {
+        let json = flat_snapshot().to_string();
+        let snapshot = serde_json::from_str::<flat::TweetSnapshot<'_>>(&json).unwrap();
+
+        let mut tables = Tables::default();
+        tables.add_flat(&snapshot).unwrap();
+
+        let ids = |batch: &RecordBatch, name: &str| {
+            batch
+                .column_by_name(name)
+                .unwrap()
+                .as_primitive::<UInt64Type>()
+                .iter()
+                .collect::<Vec<_>>()
+        };
+        let strings = |batch: &RecordBatch, name: &str| {
+            batch
+                .column_by_name(name)
+                .unwrap()
+                .as_string::<i32>()
+                .iter()
+                .map(|value| value.map(str::to_string))
+                .collect::<Vec<_>>()
+        };
+
+        // The quoted tweet is only added once (with the retweeted tweet).
+        let tweets = tables.tweets.finish().unwrap();
+
+        assert_eq!(ids(&tweets, "tweet_id"), vec![Some(4), Some(2), Some(3)]);
+        assert_eq!(ids(&tweets, "snapshot_id"), vec![Some(4), Some(4), Some(4)]);
+        assert_eq!(
+            strings(&tweets, "text")[1].as_deref(),
+            Some("@bob Look at this #long text https://t.co/AbCdEfGhIj")
+        );
+
+        let references = tables.references.finish().unwrap();
+
+        assert_eq!(
+            ids(&references, "tweet_id")
+                .into_iter()
+                .zip(ids(&references, "referenced_tweet_id"))
+                .collect::<Vec<_>>(),
+            vec![
+                (Some(4), Some(2)),
+                (Some(2), Some(1_580_530_000_000_000_001)),
+                (Some(2), Some(3))
+            ]
+        );
+
+        let entities = tables.entities.finish().unwrap();
+        let hashtags = ids(&entities, "tweet_id")
+            .into_iter()
+            .zip(strings(&entities, "text"))
+            .filter(|(_, text)| text.as_deref() == Some("long"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(hashtags, vec![(Some(2), Some("long".to_string()))]);
+
+        // The retweeted tweet has two media in its extended entities, and the quoted tweet only has
+        // the media in its entities.
+        let media = tables.media.finish().unwrap();
+
+        assert_eq!(ids(&media, "tweet_id"), vec![Some(2), Some(2), Some(3)]);
+        assert_eq!(
+            strings(&media, "media_key"),
+            vec![
+                Some("3_1580539000000000001".to_string()),
+                Some("16_1580539000000000002".to_string()),
+                Some("3_1580539000000000001".to_string())
+            ]
+        );
+
+        let withholdings = tables.withholdings.finish().unwrap();
+
+        assert_eq!(ids(&withholdings, "user_id"), vec![Some(10), Some(10)]);
+        assert_eq!(withholdings.column(0).null_count(), 2);
+    }
+}
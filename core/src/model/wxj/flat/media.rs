@@ -5,6 +5,8 @@ use serde_field_attributes::range;
 use std::borrow::Cow;
 use std::ops::Range;
 
+// Field names mirror the wire format (`media_url` etc.).
+#[allow(clippy::struct_field_names)]
 #[derive(Clone, Debug, Eq, PartialEq, ToStatic, serde::Deserialize, serde::Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Media<'a> {
@@ -31,6 +33,24 @@ pub struct Media<'a> {
     pub description: Option<Cow<'a, str>>,
 }
 
+impl Media<'_> {
+    /// The media key used in the `data` format (for example `3_1580539000000000001`).
+    ///
+    /// Videos with `amplify_video_thumb` thumbnails use the prefix `13`, and other videos (with
+    /// `ext_tw_video_thumb` thumbnails) use `7`.
+    #[must_use]
+    pub fn media_key(&self) -> String {
+        let prefix = match self.media_type {
+            MediaType::Photo => 3,
+            MediaType::Video if self.media_url.contains("/amplify_video_thumb/") => 13,
+            MediaType::Video => 7,
+            MediaType::AnimatedGif => 16,
+        };
+
+        format!("{prefix}_{}", self.id)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, ToStatic, serde::Deserialize, serde::Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct AdditionalMediaInfo<'a> {
@@ -86,3 +106,44 @@ pub struct MediaSourceMetadata {
     #[serde(with = "integer_str")]
     source_user_id_str: u64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Media;
+
+    /// The media key of a video with the given thumbnail path.
+    fn video_media_key(id: u64, thumb_path: &str) -> String {
+        let json = format!(
+            concat!(
+                r#"{{"id":{id},"id_str":"{id}","indices":[0,23],"#,
+                r#""media_url":"http://pbs.twimg.com/{thumb_path}/{id}/pu/img/AbCdEfGh.jpg","#,
+                r#""media_url_https":"https://pbs.twimg.com/{thumb_path}/{id}/pu/img/AbCdEfGh.jpg","#,
+                r#""url":"https://t.co/AbCdEfGhIj","display_url":"pic.twitter.com/AbCdEfGhIj","#,
+                r#""expanded_url":"https://twitter.com/alice/status/1/video/1","type":"video","#,
+                r#""video_info":{{"aspect_ratio":[16,9],"duration_millis":1000,"variants":[]}},"#,
+                r#""sizes":{{"thumb":{{"w":150,"h":150,"resize":"crop"}},"#,
+                r#""small":{{"w":680,"h":383,"resize":"fit"}},"#,
+                r#""medium":{{"w":1200,"h":675,"resize":"fit"}},"#,
+                r#""large":{{"w":1280,"h":720,"resize":"fit"}}}}}}"#
+            ),
+            id = id,
+            thumb_path = thumb_path
+        );
+
+        serde_json::from_str::<Media<'_>>(&json)
+            .unwrap()
+            .media_key()
+    }
+
+    #[test]
+    fn video_media_keys() {
+        assert_eq!(
+            video_media_key(1_580_539_000_000_000_003, "amplify_video_thumb"),
+            "13_1580539000000000003"
+        );
+        assert_eq!(
+            video_media_key(1_580_539_000_000_000_004, "ext_tw_video_thumb"),
+            "7_1580539000000000004"
+        );
+    }
+}
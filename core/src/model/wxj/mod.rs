@@ -1,3 +1,5 @@
+#[cfg(feature = "parquet")]
+pub mod columnar;
 pub mod data;
 pub mod flat;
 pub mod metadata;
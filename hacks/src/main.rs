@@ -1,6 +1,8 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, rust_2018_idioms)]
 #![allow(clippy::missing_errors_doc)]
 #![forbid(unsafe_code)]
+use birdsite::csv::{ColumnSet, TweetRow, Tweets};
+use birdsite::model::{tweet::Tweet, user::User};
 use cli_helpers::prelude::*;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -8,9 +10,6 @@ use std::path::PathBuf;
 
 mod db;
 
-/// Author whose tweets the `wxj extract` command selects (the Grok account).
-const GROK_USER_ID: u64 = 1_720_665_183_188_922_368;
-
 fn main() -> Result<(), Error> {
     let opts: Opts = Opts::parse();
     opts.verbose.init_logging()?;
@@ -87,7 +86,11 @@ fn main() -> Result<(), Error> {
                     }
                 }
             }
-            WxjCommand::Extract { input } => extract_tweets(input, GROK_USER_ID)?,
+            WxjCommand::Extract {
+                input,
+                author,
+                columns,
+            } => extract_tweets(input, author, &columns)?,
         },
     }
 
@@ -102,6 +105,8 @@ pub enum Error {
     Args(#[from] cli_helpers::Error),
     #[error("CSV error")]
     Csv(#[from] csv::Error),
+    #[error("CSV export error")]
+    CsvExport(#[from] birdsite::csv::Error),
     #[error("WXJ error")]
     Wxj {
         error: serde_json::Error,
@@ -154,12 +159,22 @@ enum WxjCommand {
     Extract {
         #[clap(long)]
         input: PathBuf,
+        /// User ID of the author whose tweets are selected
+        #[clap(long)]
+        author: u64,
+        /// Comma-separated column sets
+        #[clap(
+            long,
+            value_delimiter = ',',
+            default_value = "ids,timestamps,metrics,reply-target,verification"
+        )]
+        columns: Vec<ColumnSet>,
     },
 }
 
-/// Prints one CSV row for each tweet by `author_id` found in the directory of WXJ snapshot files at
+/// Writes one CSV row for each tweet by `author_id` found in the directory of WXJ snapshot files at
 /// `input`.
-fn extract_tweets(input: PathBuf, author_id: u64) -> Result<(), Error> {
+fn extract_tweets(input: PathBuf, author_id: u64, columns: &[ColumnSet]) -> Result<(), Error> {
     let mut paths = std::fs::read_dir(input)?
         .map(|entry| entry.map(|entry| entry.path()))
         .collect::<Result<Vec<_>, _>>()?;
@@ -168,44 +183,42 @@ fn extract_tweets(input: PathBuf, author_id: u64) -> Result<(), Error> {
 
     log::info!("Loaded {} paths", paths.len());
 
+    let mut writer = birdsite::csv::Writer::<_, Tweets>::new(std::io::stdout(), columns)?;
+
     for path in &paths {
         match std::fs::read_to_string(path) {
             Ok(contents) => {
                 if let Ok(snapshot) =
                     serde_json::from_str::<birdsite::model::wxj::data::TweetSnapshot<'_>>(&contents)
                 {
-                    for tweet in snapshot.includes.tweets.iter().flatten() {
+                    let author = snapshot.lookup_user(author_id).map(User::from);
+
+                    for tweet in std::iter::once(&snapshot.data)
+                        .chain(snapshot.includes.tweets.iter().flatten())
+                    {
                         if tweet.author_id == author_id {
-                            let user = snapshot.lookup_user(author_id);
-
-                            let in_reply_to_user_id = tweet.in_reply_to_user_id;
-
-                            let in_reply_to_info = in_reply_to_user_id.and_then(|user_id| {
-                                snapshot
-                                    .lookup_user(user_id)
-                                    .map(|user| (user.username.clone(), user.verified))
-                            });
-
-                            println!(
-                                "{},{},{},{},{},{},{}",
-                                tweet.author_id,
-                                tweet.id,
-                                tweet.created_at,
-                                in_reply_to_user_id
-                                    .map(|user_id| user_id.to_string())
-                                    .unwrap_or_default(),
-                                in_reply_to_info
-                                    .as_ref()
-                                    .map(|(screen_name, _)| screen_name.to_string())
-                                    .unwrap_or_default(),
-                                in_reply_to_info
-                                    .as_ref()
-                                    .map(|(_, verified)| verified.to_string())
-                                    .unwrap_or_default(),
-                                user.and_then(|user| user.public_metrics.tweet_count)
-                                    .map(|tweet_count| tweet_count.to_string())
-                                    .unwrap_or_default()
-                            );
+                            match Tweet::from_data(tweet) {
+                                Ok(tweet) => {
+                                    let in_reply_to_user = tweet
+                                        .in_reply_to_user_id
+                                        .and_then(|user_id| snapshot.lookup_user(user_id))
+                                        .map(User::from);
+
+                                    writer.write(&TweetRow {
+                                        tweet: &tweet,
+                                        author: author.as_ref(),
+                                        in_reply_to_user: in_reply_to_user.as_ref(),
+                                    })?;
+                                }
+                                Err(error) => {
+                                    log::error!(
+                                        "{} ({}): {}",
+                                        path.as_os_str().to_string_lossy(),
+                                        tweet.id,
+                                        error
+                                    );
+                                }
+                            }
                         }
                     }
                 }
@@ -216,7 +229,7 @@ fn extract_tweets(input: PathBuf, author_id: u64) -> Result<(), Error> {
         }
     }
 
-    Ok(())
+    Ok(writer.flush()?)
 }
 
 #[derive(serde::Deserialize)]